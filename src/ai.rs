@@ -0,0 +1,280 @@
+use crate::battle::{Action, BattleOutcome, BattleState, Fighter, Side};
+use crate::{Breeding, Pokemon};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+
+// Tours simulés par l'IA prévoyante après chaque paire d'actions envisagée,
+// et nombre de tirages pour moyenner le hasard des combats
+const LOOKAHEAD_TURNS: u32 = 2;
+const LOOKAHEAD_SAMPLES: u32 = 4;
+// Un combat qui s'éternise (deux Pokémons qui ne peuvent pas se toucher) est
+// arrêté au-delà de ce nombre de tours, le camp le mieux portant l'emporte
+const MAX_TURNS: u32 = 200;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Difficulty {
+    Random,
+    Greedy,
+    Lookahead,
+}
+
+impl Difficulty {
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        match name.to_lowercase().as_str() {
+            "random" | "aléatoire" | "facile" => Some(Difficulty::Random),
+            "greedy" | "glouton" | "normal" => Some(Difficulty::Greedy),
+            "lookahead" | "prévoyant" | "difficile" => Some(Difficulty::Lookahead),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Random => "aléatoire",
+            Difficulty::Greedy => "glouton",
+            Difficulty::Lookahead => "prévoyant",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Attaque au hasard parmi celles qui ont encore des PP, sans jamais changer
+fn random_action<R: Rng>(state: &BattleState, side: Side, rng: &mut R) -> Action {
+    let attacks: Vec<Action> = state
+        .legal_actions(side)
+        .into_iter()
+        .filter(|action| matches!(action, Action::Attack(_)))
+        .collect();
+    attacks[rng.gen_range(0..attacks.len())]
+}
+
+// Attaque qui inflige le plus de dégâts en moyenne au Pokémon d'en face
+fn greedy_action(state: &BattleState, side: Side) -> Action {
+    let attacker = state.active(side);
+    let defender = state.active(side.other());
+    let mut best = (Action::Attack(None), f32::MIN);
+    for action in state.legal_actions(side) {
+        if let Action::Attack(choice) = action {
+            let damage = attacker.expected_damage(choice, defender);
+            if damage > best.1 {
+                best = (action, damage);
+            }
+        }
+    }
+    best.0
+}
+
+// Un Pokémon debout vaut 1, plus la fraction de PV qu'il lui reste ; une
+// altération d'état coûte un peu
+fn team_value(team: &[Fighter]) -> f32 {
+    team.iter()
+        .filter(|f| !f.pokemon.is_fainted())
+        .map(|f| {
+            let health = f.pokemon.hp as f32 / f.pokemon.stats.hp.max(1) as f32;
+            let penalty = if f.pokemon.status.is_some() { 0.2 } else { 0.0 };
+            1.0 + health - penalty
+        })
+        .sum()
+}
+
+fn evaluate(state: &BattleState, side: Side) -> f32 {
+    team_value(state.team(side)) - team_value(state.team(side.other()))
+}
+
+// Joue une paire d'actions puis quelques tours où chacun attaque au mieux
+fn simulate(state: &BattleState, side: Side, own: Action, foe: Action, rng: &mut StdRng) -> f32 {
+    let mut state = state.clone();
+    let mut log = Vec::new();
+    let mut actions = [own, foe];
+    if side == Side::Second {
+        actions.reverse();
+    }
+    state.play_turn(actions, &mut log, rng);
+    for _ in 0..LOOKAHEAD_TURNS {
+        if state.winner().is_some() {
+            break;
+        }
+        let actions = [
+            greedy_action(&state, Side::First),
+            greedy_action(&state, Side::Second),
+        ];
+        state.play_turn(actions, &mut log, rng);
+    }
+    evaluate(&state, side)
+}
+
+// Maximin : l'action dont le pire cas, sur toutes les réponses adverses,
+// est le meilleur, chaque paire étant moyennée sur plusieurs tirages
+fn lookahead_action(state: &BattleState, side: Side, rng: &mut StdRng) -> Action {
+    let foe_actions = state.legal_actions(side.other());
+    let mut best = (Action::Attack(None), f32::MIN);
+    for own in state.legal_actions(side) {
+        let mut worst = f32::MAX;
+        for &foe in &foe_actions {
+            let total: f32 = (0..LOOKAHEAD_SAMPLES)
+                .map(|_| simulate(state, side, own, foe, rng))
+                .sum();
+            worst = worst.min(total / LOOKAHEAD_SAMPLES as f32);
+        }
+        if worst > best.1 {
+            best = (own, worst);
+        }
+    }
+    best.0
+}
+
+// Dresseur contrôlé par l'ordinateur. Son générateur a sa propre graine pour
+// que ses choix soient reproductibles quel que soit le hasard du combat.
+pub struct Opponent {
+    pub name: String,
+    pub difficulty: Difficulty,
+    pub team: Vec<Pokemon>,
+    rng: StdRng,
+}
+
+impl Opponent {
+    pub fn new(name: &str, difficulty: Difficulty, team: Vec<Pokemon>, seed: u64) -> Self {
+        Opponent {
+            name: name.to_string(),
+            difficulty,
+            team,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // L'équipe de l'adversaire est celle d'un élevage, dans l'ordre de l'équipe
+    pub fn from_breeding(
+        name: &str,
+        breeding: &Breeding,
+        difficulty: Difficulty,
+        seed: u64,
+    ) -> Option<Self> {
+        let team: Vec<Pokemon> = breeding.party().into_iter().cloned().collect();
+        if team.is_empty() {
+            return None;
+        }
+        Some(Opponent::new(name, difficulty, team, seed))
+    }
+
+    pub fn choose(&mut self, state: &BattleState, side: Side) -> Action {
+        match self.difficulty {
+            Difficulty::Random => random_action(state, side, &mut self.rng),
+            Difficulty::Greedy => greedy_action(state, side),
+            Difficulty::Lookahead => lookahead_action(state, side, &mut self.rng),
+        }
+    }
+}
+
+// Combat entre deux dresseurs contrôlés par l'ordinateur, jusqu'à ce que l'une
+// des équipes soit entièrement K.O. Les équipes sont rendues avec leurs PV,
+// altérations et XP.
+pub fn play<R: Rng>(first: &mut Opponent, second: &mut Opponent, rng: &mut R) -> BattleOutcome {
+    let mut state = BattleState::new(first.team.clone(), second.team.clone());
+    let mut log = vec![format!(
+        "{} ({}) défie {} ({}) !",
+        first.name, first.difficulty, second.name, second.difficulty
+    )];
+    while state.winner().is_none() && state.turns < MAX_TURNS {
+        let actions = [
+            first.choose(&state, Side::First),
+            second.choose(&state, Side::Second),
+        ];
+        state.play_turn(actions, &mut log, rng);
+    }
+    let winner = state
+        .winner()
+        .unwrap_or(if evaluate(&state, Side::First) >= 0.0 {
+            Side::First
+        } else {
+            Side::Second
+        });
+    let (turns, xp_gained) = (state.turns, state.xp_gained);
+    let [first_team, second_team] = state.into_teams();
+    first.team = first_team;
+    second.team = second_team;
+    BattleOutcome {
+        winner,
+        turns,
+        xp_gained,
+        log,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::pokedex;
+    use crate::Gender;
+
+    fn team(names: &[&str], rng: &mut StdRng) -> Vec<Pokemon> {
+        names
+            .iter()
+            .map(|name| {
+                let species = pokedex().find_by_name(name).unwrap().id;
+                Pokemon::new(species, 12, Gender::Male, rng)
+            })
+            .collect()
+    }
+
+    // Actions choisies par les deux camps, tour après tour, pour une graine donnée
+    fn chosen_actions(difficulty: Difficulty, seed: u64) -> Vec<[Action; 2]> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut first = Opponent::new(
+            "A",
+            difficulty,
+            team(&["Salamèche", "Pikachu"], &mut rng),
+            seed,
+        );
+        let mut second = Opponent::new(
+            "B",
+            difficulty,
+            team(&["Carapuce", "Bulbizarre"], &mut rng),
+            seed,
+        );
+        let mut state = BattleState::new(first.team.clone(), second.team.clone());
+        let mut log = Vec::new();
+        let mut chosen = Vec::new();
+        while state.winner().is_none() && state.turns < MAX_TURNS {
+            let actions = [
+                first.choose(&state, Side::First),
+                second.choose(&state, Side::Second),
+            ];
+            chosen.push(actions);
+            state.play_turn(actions, &mut log, &mut rng);
+        }
+        chosen
+    }
+
+    // Éclair et Charge ont la même puissance : seul le type les départage
+    fn pikachu_against(name: &str) -> BattleState {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut pikachu = team(&["Pikachu"], &mut rng);
+        pikachu[0].moves = vec!["Charge".to_string(), "Éclair".to_string()];
+        BattleState::new(pikachu, team(&[name], &mut rng))
+    }
+
+    #[test]
+    fn greedy_picks_the_most_damaging_move() {
+        let state = pikachu_against("Carapuce");
+        assert_eq!(greedy_action(&state, Side::First), Action::Attack(Some(1)));
+        // Plante résiste à Électrik, même avec le bonus de même type
+        let state = pikachu_against("Bulbizarre");
+        assert_eq!(greedy_action(&state, Side::First), Action::Attack(Some(0)));
+    }
+
+    #[test]
+    fn same_seed_gives_same_choices() {
+        for difficulty in [
+            Difficulty::Random,
+            Difficulty::Greedy,
+            Difficulty::Lookahead,
+        ] {
+            let chosen = chosen_actions(difficulty, 7);
+            assert!(!chosen.is_empty());
+            assert_eq!(chosen, chosen_actions(difficulty, 7), "{}", difficulty);
+        }
+    }
+}
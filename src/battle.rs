@@ -0,0 +1,693 @@
+use crate::moves::{self, Move};
+use crate::status::{Ailment, Confusion, StatusCondition, TurnCheck};
+use crate::{Pokemon, PokemonType};
+use rand::Rng;
+
+const STRUGGLE: &str = "Lutte";
+const STRUGGLE_POWER: u32 = 40;
+// Un Pokémon confus qui se blesse se frappe avec une attaque sans type de cette puissance
+const CONFUSION_POWER: u32 = 40;
+const XP_PER_LEVEL_DEFEATED: u32 = 20;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Side {
+    First,
+    Second,
+}
+
+impl Side {
+    pub fn other(self) -> Side {
+        match self {
+            Side::First => Side::Second,
+            Side::Second => Side::First,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Side::First => 0,
+            Side::Second => 1,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BattleOutcome {
+    pub winner: Side,
+    pub turns: u32,
+    pub xp_gained: u32,
+    pub log: Vec<String>,
+}
+
+impl PokemonType {
+    // Table des types : (super efficace, pas très efficace, aucun effet)
+    #[rustfmt::skip]
+    fn matchups(&self) -> (&'static [PokemonType], &'static [PokemonType], &'static [PokemonType]) {
+        use PokemonType::*;
+        match self {
+            Normal => (&[], &[Rock, Steel], &[Ghost]),
+            Fire => (&[Grass, Ice, Bug, Steel], &[Fire, Water, Rock, Dragon], &[]),
+            Water => (&[Fire, Ground, Rock], &[Water, Grass, Dragon], &[]),
+            Electric => (&[Water, Flying], &[Electric, Grass, Dragon], &[Ground]),
+            Grass => (&[Water, Ground, Rock], &[Fire, Grass, Poison, Flying, Bug, Dragon, Steel], &[]),
+            Ice => (&[Grass, Ground, Flying, Dragon], &[Fire, Water, Ice, Steel], &[]),
+            Fighting => (&[Normal, Ice, Rock, Dark, Steel], &[Poison, Flying, Psychic, Bug, Fairy], &[Ghost]),
+            Poison => (&[Grass, Fairy], &[Poison, Ground, Rock, Ghost], &[Steel]),
+            Ground => (&[Fire, Electric, Poison, Rock, Steel], &[Grass, Bug], &[Flying]),
+            Flying => (&[Grass, Fighting, Bug], &[Electric, Rock, Steel], &[]),
+            Psychic => (&[Fighting, Poison], &[Psychic, Steel], &[Dark]),
+            Bug => (&[Grass, Psychic, Dark], &[Fire, Fighting, Poison, Flying, Ghost, Steel, Fairy], &[]),
+            Rock => (&[Fire, Ice, Flying, Bug], &[Fighting, Ground, Steel], &[]),
+            Ghost => (&[Psychic, Ghost], &[Dark], &[Normal]),
+            Dragon => (&[Dragon], &[Steel], &[Fairy]),
+            Dark => (&[Psychic, Ghost], &[Fighting, Dark, Fairy], &[]),
+            Steel => (&[Ice, Rock, Fairy], &[Fire, Water, Electric, Steel], &[]),
+            Fairy => (&[Fighting, Dragon, Dark], &[Fire, Poison, Steel], &[]),
+        }
+    }
+
+    pub fn effectiveness_against(&self, defender: &PokemonType) -> f32 {
+        let (super_effective, not_very_effective, no_effect) = self.matchups();
+        if super_effective.contains(defender) {
+            2.0
+        } else if not_very_effective.contains(defender) {
+            0.5
+        } else if no_effect.contains(defender) {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    // Pour un Pokémon à deux types, les multiplicateurs se cumulent
+    pub fn effectiveness_against_pokemon(&self, defender: &Pokemon) -> f32 {
+        let secondary = match &defender.secondary_type {
+            Some(secondary_type) => self.effectiveness_against(secondary_type),
+            None => 1.0,
+        };
+        self.effectiveness_against(&defender.pokemon_type) * secondary
+    }
+}
+
+// Les PV et l'altération d'état sont ceux du Pokémon lui-même et lui restent
+// après le combat ; seules la confusion et les PP sont propres au combat
+#[derive(Clone)]
+pub struct Fighter {
+    pub pokemon: Pokemon,
+    pub moves: Vec<Move>,
+    pub pp: Vec<u32>,
+    confusion: Confusion,
+}
+
+fn struggle() -> Move {
+    Move {
+        name: STRUGGLE.to_string(),
+        move_type: PokemonType::Normal,
+        power: STRUGGLE_POWER,
+        accuracy: 100,
+        pp: 0,
+        effect: None,
+    }
+}
+
+impl Fighter {
+    fn new(pokemon: Pokemon) -> Self {
+        let moves: Vec<Move> = pokemon
+            .moves
+            .iter()
+            .filter_map(|name| moves::catalog().get(name))
+            .cloned()
+            .collect();
+        let pp = moves.iter().map(|m| m.pp).collect();
+        Fighter {
+            pokemon,
+            moves,
+            pp,
+            confusion: Confusion::default(),
+        }
+    }
+
+    // Attaques qui ont encore des PP
+    pub fn available_moves(&self) -> Vec<usize> {
+        (0..self.moves.len()).filter(|&i| self.pp[i] > 0).collect()
+    }
+
+    // Attaque choisie au hasard, None (Lutte) quand il n'en reste plus aucune
+    fn random_move<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        let available = self.available_moves();
+        if available.is_empty() {
+            return None;
+        }
+        Some(available[rng.gen_range(0..available.len())])
+    }
+
+    // Dégâts moyens de l'attaque choisie (None pour Lutte)
+    pub fn expected_damage(&self, index: Option<usize>, defender: &Fighter) -> f32 {
+        let used_move = match index {
+            Some(index) => self.moves[index].clone(),
+            None => struggle(),
+        };
+        expected_damage(&self.pokemon, &defender.pokemon, &used_move)
+    }
+
+    // Décompte un PP de l'attaque choisie ; Lutte si elle n'en a plus
+    fn take_move(&mut self, index: Option<usize>) -> Move {
+        match index {
+            Some(index) if self.pp.get(index).is_some_and(|&pp| pp > 0) => {
+                self.pp[index] -= 1;
+                self.moves[index].clone()
+            }
+            _ => struggle(),
+        }
+    }
+}
+
+// Formule des jeux : niveau de l'attaquant, puissance de l'attaque et rapport
+// Attaque / Défense, avant bonus et facteur aléatoire
+fn base_damage(attacker: &Pokemon, defender: &Pokemon, power: u32) -> f32 {
+    let attack_ratio = attacker.effective_attack() as f32 / defender.stats.defense.max(1) as f32;
+    (2.0 * attacker.level as f32 / 5.0 + 2.0) * power as f32 * attack_ratio / 50.0 + 2.0
+}
+
+// Dégâts avant le facteur aléatoire, et efficacité de l'attaque
+fn damage_before_roll(attacker: &Pokemon, defender: &Pokemon, used_move: &Move) -> (f32, f32) {
+    // Lutte ignore la table des types pour qu'un combat finisse toujours
+    let effectiveness = if used_move.name == STRUGGLE {
+        1.0
+    } else {
+        used_move.move_type.effectiveness_against_pokemon(defender)
+    };
+    if effectiveness == 0.0 {
+        return (0.0, effectiveness);
+    }
+    let same_type_bonus = if attacker.has_type(&used_move.move_type) {
+        1.5
+    } else {
+        1.0
+    };
+    let base = base_damage(attacker, defender, used_move.power);
+    (base * same_type_bonus * effectiveness, effectiveness)
+}
+
+fn damage<R: Rng>(
+    attacker: &Pokemon,
+    defender: &Pokemon,
+    used_move: &Move,
+    rng: &mut R,
+) -> (u32, f32) {
+    let (amount, effectiveness) = damage_before_roll(attacker, defender, used_move);
+    if effectiveness == 0.0 {
+        return (0, effectiveness);
+    }
+    let random_factor = rng.gen_range(0.85..=1.0);
+    ((amount * random_factor).max(1.0) as u32, effectiveness)
+}
+
+// Dégâts moyens, précision comprise : ce qu'une IA peut espérer infliger
+fn expected_damage(attacker: &Pokemon, defender: &Pokemon, used_move: &Move) -> f32 {
+    if used_move.power == 0 {
+        return 0.0;
+    }
+    let (amount, effectiveness) = damage_before_roll(attacker, defender, used_move);
+    if effectiveness == 0.0 {
+        return 0.0;
+    }
+    (amount * 0.925).max(1.0) * used_move.accuracy.min(100) as f32 / 100.0
+}
+
+fn describe_effectiveness(effectiveness: f32) -> &'static str {
+    if effectiveness == 0.0 {
+        " Ça n'a aucun effet..."
+    } else if effectiveness > 1.0 {
+        " C'est super efficace !"
+    } else if effectiveness < 1.0 {
+        " Ce n'est pas très efficace..."
+    } else {
+        ""
+    }
+}
+
+// Début de tour : sommeil, gel, paralysie et confusion peuvent empêcher
+// d'attaquer. Renvoie false si le Pokémon perd son tour.
+fn can_act<R: Rng>(fighter: &mut Fighter, log: &mut Vec<String>, rng: &mut R) -> bool {
+    if let Some(status) = fighter.pokemon.status {
+        let check = status.check_turn(rng);
+        if check != TurnCheck::Acts {
+            log.push(status.describe_turn(fighter.pokemon.name(), check));
+        }
+        match check {
+            TurnCheck::Skips => return false,
+            TurnCheck::Cured => fighter.pokemon.cure(),
+            TurnCheck::Acts => {}
+        }
+    }
+    if !fighter.confusion.is_active() {
+        return true;
+    }
+    match fighter.confusion.check_turn(rng) {
+        None => {
+            log.push(format!("{} n'est plus confus !", fighter.pokemon.name()));
+            true
+        }
+        Some(false) => {
+            log.push(format!("{} est confus...", fighter.pokemon.name()));
+            true
+        }
+        Some(true) => {
+            let pokemon = &fighter.pokemon;
+            let amount =
+                (base_damage(pokemon, pokemon, CONFUSION_POWER) * rng.gen_range(0.85..=1.0)) as u32;
+            fighter.pokemon.take_damage(amount);
+            log.push(format!(
+                "{} est confus... Il se blesse dans sa confusion ({} PV) !",
+                fighter.pokemon.name(),
+                amount
+            ));
+            false
+        }
+    }
+}
+
+fn apply_effect<R: Rng>(
+    used_move: &Move,
+    defender: &mut Fighter,
+    log: &mut Vec<String>,
+    rng: &mut R,
+) {
+    let Some(effect) = used_move.effect else {
+        return;
+    };
+    if defender.pokemon.is_fainted() || rng.gen_range(0..100) >= effect.chance {
+        return;
+    }
+    let applied = match effect.ailment {
+        Ailment::Status(status) => defender.pokemon.inflict(status),
+        Ailment::Confusion => defender.confusion.start(rng),
+    };
+    if applied {
+        log.push(match effect.ailment {
+            Ailment::Status(status) => format!("{} est {} !", defender.pokemon.name(), status),
+            Ailment::Confusion => format!("{} devient confus !", defender.pokemon.name()),
+        });
+    } else if used_move.power == 0 {
+        log.push("Mais cela échoue !".to_string());
+    }
+}
+
+// choice est l'indice de l'attaque choisie, None pour Lutte
+fn attack<R: Rng>(
+    attacker: &mut Fighter,
+    defender: &mut Fighter,
+    choice: Option<usize>,
+    log: &mut Vec<String>,
+    rng: &mut R,
+) {
+    if !can_act(attacker, log, rng) {
+        return;
+    }
+    let used_move = attacker.take_move(choice);
+    if rng.gen_range(0..100) >= used_move.accuracy {
+        log.push(format!(
+            "{} utilise {} mais rate son attaque !",
+            attacker.pokemon.name(),
+            used_move.name
+        ));
+        return;
+    }
+
+    // Les attaques de statut (puissance nulle) n'infligent aucun dégât
+    if used_move.power == 0 {
+        log.push(format!(
+            "{} utilise {} !",
+            attacker.pokemon.name(),
+            used_move.name
+        ));
+    } else {
+        let (amount, effectiveness) = damage(&attacker.pokemon, &defender.pokemon, &used_move, rng);
+        defender.pokemon.take_damage(amount);
+        log.push(format!(
+            "{} utilise {} : {} perd {} PV.{}",
+            attacker.pokemon.name(),
+            used_move.name,
+            defender.pokemon.name(),
+            amount,
+            describe_effectiveness(effectiveness)
+        ));
+        // Une cible immunisée échappe aussi à l'effet secondaire
+        if effectiveness == 0.0 {
+            return;
+        }
+        // Une attaque Feu fait fondre la glace de sa cible
+        if used_move.move_type == PokemonType::Fire
+            && defender.pokemon.status == Some(StatusCondition::Freeze)
+            && !defender.pokemon.is_fainted()
+        {
+            defender.pokemon.cure();
+            log.push(format!("{} dégèle !", defender.pokemon.name()));
+        }
+    }
+    apply_effect(&used_move, defender, log, rng);
+}
+
+// Fin de tour : brûlure et poison rongent les PV
+fn residual_damage(fighter: &mut Fighter, log: &mut Vec<String>) {
+    let Some(status) = fighter.pokemon.status else {
+        return;
+    };
+    let amount = status.residual_damage(fighter.pokemon.stats.hp);
+    if amount == 0 || fighter.pokemon.is_fainted() {
+        return;
+    }
+    fighter.pokemon.take_damage(amount);
+    log.push(status.describe_damage(fighter.pokemon.name(), amount));
+}
+
+// Le côté encore debout quand l'autre Pokémon est K.O.
+fn standing(first: &Fighter, second: &Fighter) -> Option<Side> {
+    if second.pokemon.is_fainted() {
+        Some(Side::First)
+    } else if first.pokemon.is_fainted() {
+        Some(Side::Second)
+    } else {
+        None
+    }
+}
+
+// Si l'un des deux Pokémons vient de tomber K.O., le vainqueur gagne de l'XP
+fn check_knock_out(
+    first: &mut Fighter,
+    second: &mut Fighter,
+    log: &mut Vec<String>,
+) -> Option<(Side, u32)> {
+    let winner = standing(first, second)?;
+    let (victor, loser) = match winner {
+        Side::First => (first, second),
+        Side::Second => (second, first),
+    };
+    log.push(format!("{} est K.O. !", loser.pokemon.name()));
+    let xp = loser.pokemon.level * XP_PER_LEVEL_DEFEATED;
+    log.push(format!("{} gagne {} XP.", victor.pokemon.name(), xp));
+    for event in victor.pokemon.gain_xp(xp) {
+        log.push(event.to_string());
+    }
+    Some((winner, xp))
+}
+
+fn random_attack<R: Rng>(
+    attacker: &mut Fighter,
+    defender: &mut Fighter,
+    log: &mut Vec<String>,
+    rng: &mut R,
+) {
+    let choice = attacker.random_move(rng);
+    attack(attacker, defender, choice, log, rng);
+}
+
+// La paralysie ralentit : l'ordre peut changer d'un tour à l'autre. À
+// Vitesse égale, le hasard décide.
+fn speed_order<R: Rng>(first: &Fighter, second: &Fighter, rng: &mut R) -> [Side; 2] {
+    let (first_speed, second_speed) = (
+        first.pokemon.effective_speed(),
+        second.pokemon.effective_speed(),
+    );
+    if first_speed > second_speed || (first_speed == second_speed && rng.gen_bool(0.5)) {
+        [Side::First, Side::Second]
+    } else {
+        [Side::Second, Side::First]
+    }
+}
+
+// Combat jusqu'au K.O. de l'un des deux, chacun attaquant au hasard. Les PV
+// restants et les altérations restent sur les Pokémons pour pouvoir enchaîner
+// les combats d'équipe.
+fn fight<R: Rng>(
+    first: &mut Fighter,
+    second: &mut Fighter,
+    log: &mut Vec<String>,
+    rng: &mut R,
+) -> (Side, u32, u32) {
+    if let Some(side) = standing(first, second) {
+        return (side, 0, 0);
+    }
+    let mut turns = 0;
+
+    loop {
+        turns += 1;
+        let order = speed_order(first, second, rng);
+        for side in order {
+            match side {
+                Side::First => random_attack(first, second, log, rng),
+                Side::Second => random_attack(second, first, log, rng),
+            }
+            if let Some((winner, xp)) = check_knock_out(first, second, log) {
+                return (winner, turns, xp);
+            }
+        }
+        for side in order {
+            match side {
+                Side::First => residual_damage(first, log),
+                Side::Second => residual_damage(second, log),
+            }
+            if let Some((winner, xp)) = check_knock_out(first, second, log) {
+                return (winner, turns, xp);
+            }
+        }
+    }
+}
+
+pub fn duel<R: Rng>(first: &mut Pokemon, second: &mut Pokemon, rng: &mut R) -> BattleOutcome {
+    let mut first_fighter = Fighter::new(first.clone());
+    let mut second_fighter = Fighter::new(second.clone());
+    let mut log = Vec::new();
+    let (winner, turns, xp_gained) = fight(&mut first_fighter, &mut second_fighter, &mut log, rng);
+    *first = first_fighter.pokemon;
+    *second = second_fighter.pokemon;
+    BattleOutcome {
+        winner,
+        turns,
+        xp_gained,
+        log,
+    }
+}
+
+pub fn team_battle<R: Rng>(
+    first_team: &mut [Pokemon],
+    second_team: &mut [Pokemon],
+    rng: &mut R,
+) -> BattleOutcome {
+    let fighters =
+        |team: &[Pokemon]| -> Vec<Fighter> { team.iter().cloned().map(Fighter::new).collect() };
+    let mut first_fighters = fighters(first_team);
+    let mut second_fighters = fighters(second_team);
+    let mut log = Vec::new();
+    let mut turns = 0;
+    let mut xp_gained = 0;
+
+    let winner = loop {
+        let first = first_fighters.iter_mut().find(|f| !f.pokemon.is_fainted());
+        let second = second_fighters.iter_mut().find(|f| !f.pokemon.is_fainted());
+        match (first, second) {
+            (Some(first), Some(second)) => {
+                log.push(format!(
+                    "{} affronte {} !",
+                    first.pokemon.name(),
+                    second.pokemon.name()
+                ));
+                let (_, fight_turns, xp) = fight(first, second, &mut log, rng);
+                turns += fight_turns;
+                xp_gained += xp;
+            }
+            (Some(_), None) => break Side::First,
+            (None, _) => break Side::Second,
+        }
+    };
+
+    for (pokemon, fighter) in first_team
+        .iter_mut()
+        .zip(first_fighters)
+        .chain(second_team.iter_mut().zip(second_fighters))
+    {
+        *pokemon = fighter.pokemon;
+    }
+    BattleOutcome {
+        winner,
+        turns,
+        xp_gained,
+        log,
+    }
+}
+
+// Ce qu'un dresseur décide pour son Pokémon actif au début du tour
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Action {
+    // Indice de l'attaque, None pour Lutte
+    Attack(Option<usize>),
+    // Position dans l'équipe du Pokémon qui prend le relais
+    Switch(usize),
+}
+
+// Combat de dresseurs, un contre un : chaque camp a un Pokémon actif, les
+// autres attendent leur tour. L'état se clone pour que l'IA puisse simuler
+// les tours à venir.
+#[derive(Clone)]
+pub struct BattleState {
+    teams: [Vec<Fighter>; 2],
+    active: [usize; 2],
+    pub turns: u32,
+    pub xp_gained: u32,
+}
+
+impl BattleState {
+    pub fn new(first_team: Vec<Pokemon>, second_team: Vec<Pokemon>) -> Self {
+        let teams = [first_team, second_team]
+            .map(|team| team.into_iter().map(Fighter::new).collect::<Vec<_>>());
+        let active = [&teams[0], &teams[1]].map(|team| {
+            team.iter()
+                .position(|f| !f.pokemon.is_fainted())
+                .unwrap_or(0)
+        });
+        BattleState {
+            teams,
+            active,
+            turns: 0,
+            xp_gained: 0,
+        }
+    }
+
+    pub fn team(&self, side: Side) -> &[Fighter] {
+        &self.teams[side.index()]
+    }
+
+    pub fn active_index(&self, side: Side) -> usize {
+        self.active[side.index()]
+    }
+
+    pub fn active(&self, side: Side) -> &Fighter {
+        &self.team(side)[self.active_index(side)]
+    }
+
+    // Le camp gagnant, quand toute l'équipe adverse est K.O.
+    pub fn winner(&self) -> Option<Side> {
+        let defeated = |side: Side| self.team(side).iter().all(|f| f.pokemon.is_fainted());
+        if defeated(Side::Second) {
+            Some(Side::First)
+        } else if defeated(Side::First) {
+            Some(Side::Second)
+        } else {
+            None
+        }
+    }
+
+    pub fn legal_actions(&self, side: Side) -> Vec<Action> {
+        let mut actions: Vec<Action> = self
+            .active(side)
+            .available_moves()
+            .into_iter()
+            .map(|index| Action::Attack(Some(index)))
+            .collect();
+        if actions.is_empty() {
+            actions.push(Action::Attack(None));
+        }
+        actions.extend(
+            self.team(side)
+                .iter()
+                .enumerate()
+                .filter(|(index, f)| *index != self.active_index(side) && !f.pokemon.is_fainted())
+                .map(|(index, _)| Action::Switch(index)),
+        );
+        actions
+    }
+
+    // Le Pokémon actif du camp puis celui d'en face
+    fn fighters(&mut self, side: Side) -> (&mut Fighter, &mut Fighter) {
+        let [first_team, second_team] = &mut self.teams;
+        let first = &mut first_team[self.active[0]];
+        let second = &mut second_team[self.active[1]];
+        match side {
+            Side::First => (first, second),
+            Side::Second => (second, first),
+        }
+    }
+
+    fn switch(&mut self, side: Side, index: usize, log: &mut Vec<String>) {
+        let current = self.active[side.index()];
+        let team = &mut self.teams[side.index()];
+        if index == current || team.get(index).is_none_or(|f| f.pokemon.is_fainted()) {
+            return;
+        }
+        // La confusion disparaît quand le Pokémon retourne dans sa Poké Ball
+        team[current].confusion = Confusion::default();
+        log.push(format!(
+            "{} revient ! {} entre en combat !",
+            team[current].pokemon.name(),
+            team[index].pokemon.name()
+        ));
+        self.active[side.index()] = index;
+    }
+
+    fn check_knock_out(&mut self, log: &mut Vec<String>) -> bool {
+        let (first, second) = self.fighters(Side::First);
+        match check_knock_out(first, second, log) {
+            Some((_, xp)) => {
+                self.xp_gained += xp;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Après un K.O., le premier Pokémon encore debout de l'équipe prend le relais
+    fn replace_fainted(&mut self, log: &mut Vec<String>) {
+        for (team, active) in self.teams.iter().zip(self.active.iter_mut()) {
+            if !team[*active].pokemon.is_fainted() {
+                continue;
+            }
+            if let Some(next) = team.iter().position(|f| !f.pokemon.is_fainted()) {
+                *active = next;
+                log.push(format!("{} entre en combat !", team[next].pokemon.name()));
+            }
+        }
+    }
+
+    // Les changements de Pokémon passent avant les attaques, puis les deux
+    // Pokémons actifs attaquent dans l'ordre de leur Vitesse
+    pub fn play_turn<R: Rng>(&mut self, actions: [Action; 2], log: &mut Vec<String>, rng: &mut R) {
+        if self.winner().is_some() {
+            return;
+        }
+        self.turns += 1;
+        for side in [Side::First, Side::Second] {
+            if let Action::Switch(index) = actions[side.index()] {
+                self.switch(side, index, log);
+            }
+        }
+
+        let order = speed_order(self.active(Side::First), self.active(Side::Second), rng);
+        let mut knocked_out = false;
+        for side in order {
+            let Action::Attack(choice) = actions[side.index()] else {
+                continue;
+            };
+            let (attacker, defender) = self.fighters(side);
+            attack(attacker, defender, choice, log, rng);
+            knocked_out = self.check_knock_out(log);
+            if knocked_out {
+                break;
+            }
+        }
+        if !knocked_out {
+            for side in order {
+                residual_damage(self.fighters(side).0, log);
+                if self.check_knock_out(log) {
+                    break;
+                }
+            }
+        }
+        self.replace_fainted(log);
+    }
+
+    pub fn into_teams(self) -> [Vec<Pokemon>; 2] {
+        self.teams
+            .map(|team| team.into_iter().map(|f| f.pokemon).collect())
+    }
+}
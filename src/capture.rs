@@ -0,0 +1,129 @@
+use crate::{Breeding, Pokemon};
+use rand::Rng;
+use std::fmt;
+
+// Nombre de secousses de la balle avant que la capture soit réussie
+const SHAKES: u32 = 4;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Ball {
+    Poke,
+    Super,
+    Hyper,
+    Master,
+}
+
+impl Ball {
+    pub const ALL: [Ball; 4] = [Ball::Poke, Ball::Super, Ball::Hyper, Ball::Master];
+
+    // None pour la Master Ball, qui réussit toujours
+    fn bonus(&self) -> Option<f32> {
+        match self {
+            Ball::Poke => Some(1.0),
+            Ball::Super => Some(1.5),
+            Ball::Hyper => Some(2.0),
+            Ball::Master => None,
+        }
+    }
+
+    // Accepte « super », « superball » ou « Super Ball »
+    pub fn from_name(name: &str) -> Option<Ball> {
+        let name: String = name
+            .to_lowercase()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        let name = name.trim_end_matches("ball");
+        match name {
+            "poke" | "poké" => Some(Ball::Poke),
+            "super" | "great" => Some(Ball::Super),
+            "hyper" | "ultra" => Some(Ball::Hyper),
+            "master" => Some(Ball::Master),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Ball {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Ball::Poke => "Poké Ball",
+            Ball::Super => "Super Ball",
+            Ball::Hyper => "Hyper Ball",
+            Ball::Master => "Master Ball",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CaptureResult {
+    Caught(u32),
+    BrokeFree { shakes: u32 },
+}
+
+impl fmt::Display for CaptureResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CaptureResult::Caught(id) => write!(f, "Capturé ! (n°{})", id),
+            CaptureResult::BrokeFree { shakes: 0 } => {
+                write!(f, "Le Pokémon s'est libéré aussitôt !")
+            }
+            CaptureResult::BrokeFree { shakes } => write!(
+                f,
+                "La balle a bougé {} fois... mais le Pokémon s'est libéré !",
+                shakes
+            ),
+        }
+    }
+}
+
+// Taux de capture modifié (formule de la 3e génération) : moins il reste de
+// PV, plus la balle est efficace et plus l'altération d'état est lourde,
+// plus il est élevé. 255 ou plus garantit la capture.
+fn modified_catch_rate(wild: &Pokemon, ball: Ball) -> Option<f32> {
+    let ball_bonus = ball.bonus()?;
+    let max_hp = wild.stats.hp.max(1) as f32;
+    let hp = wild.hp.min(wild.stats.hp) as f32;
+    let catch_rate = Pokemon::species_info(wild.species).catch_rate as f32;
+    let status_bonus = wild.status.map_or(1.0, |s| s.catch_bonus());
+    Some((3.0 * max_hp - 2.0 * hp) * catch_rate * ball_bonus / (3.0 * max_hp) * status_bonus)
+}
+
+// Probabilité qu'une secousse réussisse, sur 65536
+fn shake_threshold(rate: f32) -> Option<u32> {
+    if rate >= 255.0 {
+        return None;
+    }
+    let rate = rate.max(1.0);
+    Some((1_048_560.0 / (16_711_680.0 / rate).sqrt().sqrt()) as u32)
+}
+
+pub fn catch_probability(wild: &Pokemon, ball: Ball) -> f32 {
+    match modified_catch_rate(wild, ball).and_then(shake_threshold) {
+        None => 1.0,
+        Some(threshold) => (threshold as f32 / 65536.0).powi(SHAKES as i32),
+    }
+}
+
+// Nombre de secousses réussies, SHAKES signifiant que le Pokémon est capturé
+pub fn throw_ball<R: Rng>(wild: &Pokemon, ball: Ball, rng: &mut R) -> u32 {
+    let Some(threshold) = modified_catch_rate(wild, ball).and_then(shake_threshold) else {
+        return SHAKES;
+    };
+    (0..SHAKES)
+        .take_while(|_| rng.gen_range(0..65536) < threshold)
+        .count() as u32
+}
+
+impl Breeding {
+    // Lance une balle sur un Pokémon sauvage ; s'il est capturé, il rejoint
+    // l'élevage (équipe ou PC) avec ses PV et son état, et son nouvel
+    // identifiant est renvoyé
+    pub fn capture<R: Rng>(&mut self, wild: Pokemon, ball: Ball, rng: &mut R) -> CaptureResult {
+        match throw_ball(&wild, ball, rng) {
+            SHAKES => CaptureResult::Caught(self.add_pokemon(wild)),
+            shakes => CaptureResult::BrokeFree { shakes },
+        }
+    }
+}
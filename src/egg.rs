@@ -0,0 +1,32 @@
+use crate::Pokemon;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub const STEPS_PER_EGG_CYCLE: u32 = 10;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Egg {
+    pub pokemon: Pokemon,
+    pub steps_remaining: u32,
+}
+
+impl Egg {
+    pub fn new(pokemon: Pokemon) -> Self {
+        let steps_remaining = pokemon.species().egg_cycles * STEPS_PER_EGG_CYCLE;
+        Egg {
+            pokemon,
+            steps_remaining,
+        }
+    }
+
+    pub fn advance(&mut self, steps: u32) -> bool {
+        self.steps_remaining = self.steps_remaining.saturating_sub(steps);
+        self.steps_remaining == 0
+    }
+}
+
+impl fmt::Display for Egg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Oeuf ({} pas avant l'éclosion)", self.steps_remaining)
+    }
+}
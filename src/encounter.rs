@@ -0,0 +1,96 @@
+use crate::pokedex::pokedex;
+use crate::Pokemon;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+use serde::Deserialize;
+use std::fmt;
+use std::sync::OnceLock;
+
+// Une ligne de la table de rencontres : plus la rareté est élevée, plus
+// l'espèce apparaît souvent (poids relatif, pas un pourcentage)
+#[derive(Debug, Clone, Deserialize)]
+pub struct EncounterSlot {
+    pub species: u32,
+    pub min_level: u32,
+    pub max_level: u32,
+    pub rarity: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Zone {
+    pub id: String,
+    pub name: String,
+    pub encounters: Vec<EncounterSlot>,
+}
+
+impl Zone {
+    // Tire l'espèce selon les poids de la table, puis le niveau, le sexe, les
+    // IV et la nature du Pokémon sauvage
+    pub fn roll<R: Rng>(&self, rng: &mut R) -> Option<Pokemon> {
+        let weights = WeightedIndex::new(self.encounters.iter().map(|e| e.rarity)).ok()?;
+        let slot = &self.encounters[weights.sample(rng)];
+        let level = rng.gen_range(slot.min_level..=slot.max_level.max(slot.min_level));
+        let gender = Pokemon::species_info(slot.species).random_gender(rng);
+        Some(Pokemon::new(slot.species, level, gender, rng))
+    }
+
+    // Probabilité de rencontrer chaque espèce de la zone
+    pub fn odds(&self) -> Vec<(u32, f32)> {
+        let total: u32 = self.encounters.iter().map(|e| e.rarity).sum();
+        self.encounters
+            .iter()
+            .map(|e| (e.species, e.rarity as f32 / total.max(1) as f32))
+            .collect()
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.id)?;
+        for (slot, (_, odds)) in self.encounters.iter().zip(self.odds()) {
+            write!(
+                f,
+                "\n  {} niv. {}-{} : {:.0}%",
+                Pokemon::species_info(slot.species).name_fr,
+                slot.min_level,
+                slot.max_level,
+                odds * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Zones {
+    zones: Vec<Zone>,
+}
+
+impl Zones {
+    pub fn all(&self) -> &[Zone] {
+        &self.zones
+    }
+
+    // Par identifiant (route-1) ou par nom affiché (Route 1)
+    pub fn find(&self, name: &str) -> Option<&Zone> {
+        self.zones.iter().find(|zone| {
+            zone.id.eq_ignore_ascii_case(name) || zone.name.to_lowercase() == name.to_lowercase()
+        })
+    }
+}
+
+pub fn zones() -> &'static Zones {
+    static ZONES: OnceLock<Zones> = OnceLock::new();
+    ZONES.get_or_init(|| {
+        let zones: Zones =
+            serde_json::from_str(include_str!("../zones.json")).expect("zones.json invalide");
+        for slot in zones.zones.iter().flat_map(|zone| &zone.encounters) {
+            assert!(
+                pokedex().get(slot.species).is_some(),
+                "zones.json: espèce {} absente du Pokédex",
+                slot.species
+            );
+        }
+        zones
+    })
+}
@@ -0,0 +1,59 @@
+use crate::moves;
+use crate::stats::{Nature, Stats, MAX_IV};
+use crate::Pokemon;
+use rand::Rng;
+
+const MUTATION_CHANCE: f64 = 0.1;
+const NATURE_INHERITANCE_CHANCE: f64 = 0.5;
+
+fn inherit_iv<R: Rng>(mother: u32, father: u32, rng: &mut R) -> u32 {
+    if rng.gen_bool(MUTATION_CHANCE) {
+        rng.gen_range(0..=MAX_IV)
+    } else if rng.gen_bool(0.5) {
+        mother
+    } else {
+        father
+    }
+}
+
+// Chaque IV vient au hasard de l'un des deux parents, avec une petite
+// chance de mutation qui le tire à nouveau
+pub fn crossover_ivs<R: Rng>(mother: &Stats, father: &Stats, rng: &mut R) -> Stats {
+    Stats {
+        hp: inherit_iv(mother.hp, father.hp, rng),
+        attack: inherit_iv(mother.attack, father.attack, rng),
+        defense: inherit_iv(mother.defense, father.defense, rng),
+        speed: inherit_iv(mother.speed, father.speed, rng),
+    }
+}
+
+// Consanguinité : plus les parents sont proches, plus les IV du bébé s'affaiblissent
+pub fn inbreeding_depression(ivs: &Stats, relatedness: f32) -> Stats {
+    let weaken = |iv: u32| (iv as f32 * (1.0 - relatedness.clamp(0.0, 1.0))) as u32;
+    Stats {
+        hp: weaken(ivs.hp),
+        attack: weaken(ivs.attack),
+        defense: weaken(ivs.defense),
+        speed: weaken(ivs.speed),
+    }
+}
+
+pub fn inherit_nature<R: Rng>(mother: &Nature, father: &Nature, rng: &mut R) -> Nature {
+    if !rng.gen_bool(NATURE_INHERITANCE_CHANCE) {
+        Nature::random(rng)
+    } else if rng.gen_bool(0.5) {
+        mother.clone()
+    } else {
+        father.clone()
+    }
+}
+
+// Les attaques connues des deux parents sont transmises si le bébé peut les apprendre
+pub fn inherited_moves<'a>(mother: &'a Pokemon, father: &Pokemon, species: u32) -> Vec<&'a str> {
+    mother
+        .moves
+        .iter()
+        .filter(|m| father.moves.contains(m) && moves::catalog().can_learn(species, m))
+        .map(String::as_str)
+        .collect()
+}
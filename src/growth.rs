@@ -0,0 +1,46 @@
+use serde::Deserialize;
+
+pub const MAX_LEVEL: u32 = 100;
+
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+pub enum GrowthRate {
+    Fast,
+    MediumFast,
+    MediumSlow,
+    Slow,
+    Erratic,
+    Fluctuating,
+}
+
+impl GrowthRate {
+    // Expérience totale nécessaire pour atteindre un niveau, selon les courbes des jeux
+    pub fn total_xp_for_level(&self, level: u32) -> u32 {
+        if level <= 1 {
+            return 0;
+        }
+        let n = level.min(MAX_LEVEL) as i64;
+        let cube = n * n * n;
+        let total = match self {
+            GrowthRate::Fast => 4 * cube / 5,
+            GrowthRate::MediumFast => cube,
+            GrowthRate::MediumSlow => 6 * cube / 5 - 15 * n * n + 100 * n - 140,
+            GrowthRate::Slow => 5 * cube / 4,
+            GrowthRate::Erratic => match n {
+                n if n < 50 => cube * (100 - n) / 50,
+                n if n < 68 => cube * (150 - n) / 100,
+                n if n < 98 => cube * ((1911 - 10 * n) / 3) / 500,
+                n => cube * (160 - n) / 100,
+            },
+            GrowthRate::Fluctuating => match n {
+                n if n < 15 => cube * ((n + 1) / 3 + 24) / 50,
+                n if n < 36 => cube * (n + 14) / 50,
+                n => cube * (n / 2 + 32) / 50,
+            },
+        };
+        total.max(0) as u32
+    }
+
+    pub fn xp_between_levels(&self, level: u32) -> u32 {
+        self.total_xp_for_level(level + 1) - self.total_xp_for_level(level)
+    }
+}
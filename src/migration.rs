@@ -0,0 +1,166 @@
+use crate::growth::MAX_LEVEL;
+use crate::pokedex::{pokedex, Species};
+use crate::storage::invalid_data;
+use crate::PokemonType;
+use serde_json::{Map, Value};
+use std::io;
+
+// Ancienne règle d'expérience : 100 points par niveau pour toutes les espèces
+const LEGACY_XP_PER_LEVEL: u64 = 100;
+
+// Version 1 : sauvegardes d'origine, sans champ « version », où chaque
+// Pokémon n'avait qu'un « name » au lieu d'un numéro d'espèce et une
+// expérience comptée selon l'ancienne règle.
+// Version 2 : numéro d'espèce dans le Pokédex.
+pub const CURRENT_VERSION: u64 = 2;
+
+type Migration = fn(&mut Value) -> Result<(), String>;
+
+// MIGRATIONS[i] fait passer une sauvegarde de la version i + 1 à i + 2
+const MIGRATIONS: [Migration; 1] = [species_from_name];
+
+fn pokemon_entries(save: &mut Value) -> Vec<&mut Value> {
+    let mut entries = Vec::new();
+    let Some(save) = save.as_object_mut() else {
+        return entries;
+    };
+    for (key, list) in save.iter_mut() {
+        let Some(list) = list.as_array_mut() else {
+            continue;
+        };
+        match key.as_str() {
+            "pokemon_list" => entries.extend(list.iter_mut()),
+            "eggs" => entries.extend(list.iter_mut().filter_map(|egg| egg.get_mut("pokemon"))),
+            _ => {}
+        }
+    }
+    entries
+}
+
+fn species_from_name(save: &mut Value) -> Result<(), String> {
+    for pokemon in pokemon_entries(save) {
+        let Some(pokemon) = pokemon.as_object_mut() else {
+            return Err("Pokémon mal formé".to_string());
+        };
+        if pokemon.contains_key("species") {
+            continue;
+        }
+        let name = pokemon
+            .remove("name")
+            .and_then(|name| name.as_str().map(str::to_string))
+            .ok_or("Pokémon sans espèce ni nom")?;
+        let species = match pokedex().find_by_name(&name) {
+            Some(species) => species,
+            // Les petits de l'ancien élevage s'appelaient tous « Mystere » et
+            // prenaient le type de leur mère : on garde leur nom en surnom
+            None => {
+                let species = species_of_type(pokemon)
+                    .ok_or(format!("Espèce inconnue dans le Pokédex: {}", name))?;
+                pokemon.entry("nickname").or_insert(name.into());
+                species
+            }
+        };
+        pokemon.insert("species".to_string(), species.id.into());
+        rescale_xp(pokemon, species);
+    }
+    Ok(())
+}
+
+// Première espèce du Pokédex dont le type principal est celui de la sauvegarde
+fn species_of_type(pokemon: &Map<String, Value>) -> Option<&'static Species> {
+    let pokemon_type: PokemonType =
+        serde_json::from_value(pokemon.get("pokemon_type")?.clone()).ok()?;
+    pokedex()
+        .all()
+        .iter()
+        .find(|species| species.types.first() == Some(&pokemon_type))
+}
+
+// L'expérience gardait la progression dans le niveau sur 100 points : on la
+// reporte à la même proportion de la courbe de l'espèce
+fn rescale_xp(pokemon: &mut Map<String, Value>, species: &Species) {
+    let Some(xp) = pokemon.get("xp").and_then(Value::as_u64) else {
+        return;
+    };
+    let level = pokemon
+        .get("level")
+        .and_then(Value::as_u64)
+        .map_or(1, |level| level.min(MAX_LEVEL as u64) as u32);
+    let to_next_level = species.growth_rate.xp_between_levels(level) as u64;
+    let xp = xp.min(LEGACY_XP_PER_LEVEL - 1) * to_next_level / LEGACY_XP_PER_LEVEL;
+    pokemon.insert("xp".to_string(), xp.into());
+}
+
+pub fn stamp(save: &mut Value) {
+    if let Some(save) = save.as_object_mut() {
+        save.insert("version".to_string(), CURRENT_VERSION.into());
+    }
+}
+
+// Met à niveau une sauvegarde lue depuis le disque, étape par étape
+pub fn migrate(save: &mut Value) -> io::Result<()> {
+    let version = match save.get("version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .filter(|&v| v >= 1)
+            .ok_or_else(|| invalid_data(format!("Version de sauvegarde invalide: {}", version)))?,
+    };
+    if version > CURRENT_VERSION {
+        return Err(invalid_data(format!(
+            "Sauvegarde écrite par une version plus récente du programme (version {}, \
+             version prise en charge: {}), mettez le programme à jour",
+            version, CURRENT_VERSION
+        )));
+    }
+
+    for (step, migration) in MIGRATIONS.iter().enumerate().skip(version as usize - 1) {
+        migration(save).map_err(|message| {
+            invalid_data(format!(
+                "Migration de la version {} vers {} impossible: {}",
+                step + 1,
+                step + 2,
+                message
+            ))
+        })?;
+    }
+    stamp(save);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_original_save() {
+        let mut save: Value =
+            serde_json::from_str(include_str!("../fixtures/save_v1.json")).unwrap();
+        save["pokemon_list"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({
+                "name": "Mystere",
+                "level": 1,
+                "pokemon_type": "Water",
+                "xp": 0,
+                "gender": "Female"
+            }));
+        migrate(&mut save).unwrap();
+
+        let salameche = &save["pokemon_list"][0];
+        let species = pokedex().find_by_name("Salamèche").unwrap();
+        assert_eq!(salameche["species"], species.id);
+        assert_eq!(
+            salameche["xp"],
+            species.growth_rate.xp_between_levels(5) / 2
+        );
+
+        let mystere = &save["pokemon_list"][4];
+        assert_eq!(
+            mystere["species"],
+            pokedex().find_by_name("Carapuce").unwrap().id
+        );
+        assert_eq!(mystere["nickname"], "Mystere");
+    }
+}
@@ -0,0 +1,66 @@
+use crate::status::MoveEffect;
+use crate::PokemonType;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+pub const MAX_KNOWN_MOVES: usize = 4;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Move {
+    pub name: String,
+    pub move_type: PokemonType,
+    pub power: u32,
+    pub accuracy: u32,
+    pub pp: u32,
+    #[serde(default)]
+    pub effect: Option<MoveEffect>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LearnsetEntry {
+    level: u32,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MoveCatalog {
+    moves: Vec<Move>,
+    learnsets: HashMap<u32, Vec<LearnsetEntry>>,
+}
+
+impl MoveCatalog {
+    pub fn get(&self, name: &str) -> Option<&Move> {
+        self.moves.iter().find(|m| m.name == name)
+    }
+
+    pub fn learned_at(&self, species: u32, level: u32) -> Vec<&str> {
+        self.learnset(species)
+            .filter(|entry| entry.level == level)
+            .map(|entry| entry.name.as_str())
+            .collect()
+    }
+
+    pub fn learned_up_to(&self, species: u32, level: u32) -> Vec<&str> {
+        self.learnset(species)
+            .filter(|entry| entry.level <= level)
+            .map(|entry| entry.name.as_str())
+            .collect()
+    }
+
+    pub fn can_learn(&self, species: u32, name: &str) -> bool {
+        self.learnset(species).any(|entry| entry.name == name)
+    }
+
+    fn learnset(&self, species: u32) -> impl Iterator<Item = &LearnsetEntry> {
+        self.learnsets.get(&species).into_iter().flatten()
+    }
+}
+
+// Le catalogue est embarqué dans l'exécutable et chargé une seule fois
+pub fn catalog() -> &'static MoveCatalog {
+    static CATALOG: OnceLock<MoveCatalog> = OnceLock::new();
+    CATALOG.get_or_init(|| {
+        serde_json::from_str(include_str!("../moves.json")).expect("moves.json invalide")
+    })
+}
@@ -0,0 +1,286 @@
+use crate::{Breeding, BreedingRefusal, Pokemon};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+// Au-delà de ce coefficient, les parents sont trop proches : des demi-frères
+// et soeurs (0.25) sont encore tolérés, pas des frères et soeurs (0.5)
+pub const DEFAULT_MAX_RELATEDNESS: f32 = 0.25;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub enum InbreedingPolicy {
+    Refuse,
+    Penalise,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub struct InbreedingRules {
+    pub max_relatedness: f32,
+    pub policy: InbreedingPolicy,
+}
+
+impl Default for InbreedingRules {
+    fn default() -> Self {
+        InbreedingRules {
+            max_relatedness: DEFAULT_MAX_RELATEDNESS,
+            policy: InbreedingPolicy::Refuse,
+        }
+    }
+}
+
+impl InbreedingRules {
+    pub fn exceeds(&self, relatedness: f32) -> bool {
+        relatedness > self.max_relatedness
+    }
+}
+
+impl Pokemon {
+    fn parent_ids(&self) -> impl Iterator<Item = u32> {
+        self.mother.into_iter().chain(self.father)
+    }
+
+    fn pedigree_label(&self) -> String {
+        format!("{}. {} (Gén. {})", self.id, self.name(), self.generation)
+    }
+}
+
+impl Breeding {
+    // Les oeufs font partie de la famille même s'ils n'ont pas encore éclos
+    pub fn find(&self, id: u32) -> Option<&Pokemon> {
+        self.all_pokemon().find(|p| p.id == id)
+    }
+
+    fn children(&self, id: u32) -> Vec<&Pokemon> {
+        self.all_pokemon()
+            .filter(|p| p.mother == Some(id) || p.father == Some(id))
+            .collect()
+    }
+
+    // Parcours en largeur : parents d'abord, puis grands-parents, etc.
+    // Un ancêtre commun aux deux lignées n'apparaît qu'une fois.
+    pub fn ancestors(&self, id: u32) -> Vec<&Pokemon> {
+        let mut seen = HashSet::new();
+        let mut result = Vec::new();
+        let mut queue: Vec<u32> = match self.find(id) {
+            Some(pokemon) => pokemon.parent_ids().collect(),
+            None => return result,
+        };
+        while !queue.is_empty() {
+            let mut next = Vec::new();
+            for parent_id in queue {
+                if !seen.insert(parent_id) {
+                    continue;
+                }
+                // Un parent qui a quitté l'élevage interrompt la lignée
+                if let Some(parent) = self.find(parent_id) {
+                    next.extend(parent.parent_ids());
+                    result.push(parent);
+                }
+            }
+            queue = next;
+        }
+        result
+    }
+
+    pub fn descendants(&self, id: u32) -> Vec<&Pokemon> {
+        let mut seen = HashSet::new();
+        let mut result = Vec::new();
+        let mut queue = vec![id];
+        while let Some(current) = queue.pop() {
+            for child in self.children(current) {
+                if seen.insert(child.id) {
+                    queue.push(child.id);
+                    result.push(child);
+                }
+            }
+        }
+        result.sort_by_key(|p| (p.generation, p.id));
+        result
+    }
+
+    // Graphe Graphviz de toute la famille : ancêtres et descendants, chaque
+    // flèche allant d'un parent vers son petit
+    pub fn lineage_to_dot(&self, id: u32) -> Option<String> {
+        let root = self.find(id)?;
+        let mut family = vec![root];
+        family.extend(self.ancestors(id));
+        family.extend(self.descendants(id));
+        let members: HashSet<u32> = family.iter().map(|p| p.id).collect();
+
+        let mut dot = String::from("digraph lignee {\n    node [shape=box];\n");
+        for pokemon in &family {
+            let style = if pokemon.id == id { ", style=bold" } else { "" };
+            dot.push_str(&format!(
+                "    p{} [label=\"{}\"{}];\n",
+                pokemon.id,
+                pokemon
+                    .pedigree_label()
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\""),
+                style
+            ));
+        }
+        for pokemon in &family {
+            for (parent, role) in [(pokemon.mother, "mère"), (pokemon.father, "père")] {
+                if let Some(parent_id) = parent.filter(|p| members.contains(p)) {
+                    dot.push_str(&format!(
+                        "    p{} -> p{} [label=\"{}\"];\n",
+                        parent_id, pokemon.id, role
+                    ));
+                }
+            }
+        }
+        dot.push_str("}\n");
+        Some(dot)
+    }
+
+    // Arbre indenté : l'ascendance en remontant, puis la descendance
+    pub fn lineage_to_text(&self, id: u32) -> Option<String> {
+        let root = self.find(id)?;
+        let mut text = format!("{}\n", root.pedigree_label());
+        self.write_parents(root, 1, &mut text);
+
+        let children = self.children(id);
+        if !children.is_empty() {
+            text.push_str("Descendance:\n");
+            for child in children {
+                self.write_children(child, 1, &mut text);
+            }
+        }
+        Some(text)
+    }
+
+    fn write_parents(&self, pokemon: &Pokemon, depth: usize, text: &mut String) {
+        let indent = "  ".repeat(depth);
+        for (parent, role) in [(pokemon.mother, "Mère"), (pokemon.father, "Père")] {
+            let Some(parent_id) = parent else {
+                continue;
+            };
+            match self.find(parent_id) {
+                Some(parent) => {
+                    text.push_str(&format!(
+                        "{}{}: {}\n",
+                        indent,
+                        role,
+                        parent.pedigree_label()
+                    ));
+                    self.write_parents(parent, depth + 1, text);
+                }
+                None => text.push_str(&format!(
+                    "{}{}: {}. (a quitté l'élevage)\n",
+                    indent, role, parent_id
+                )),
+            }
+        }
+    }
+
+    fn write_children(&self, pokemon: &Pokemon, depth: usize, text: &mut String) {
+        text.push_str(&format!(
+            "{}{}\n",
+            "  ".repeat(depth),
+            pokemon.pedigree_label()
+        ));
+        for child in self.children(pokemon.id) {
+            self.write_children(child, depth + 1, text);
+        }
+    }
+
+    // Coefficient de parenté (méthode tabulaire) : un parent a toujours un
+    // identifiant plus petit que son petit, on remonte donc toujours le plus jeune
+    fn kinship(&self, id1: u32, id2: u32) -> f32 {
+        if id1 == id2 {
+            let inbreeding = match self.find(id1) {
+                Some(Pokemon {
+                    mother: Some(mother),
+                    father: Some(father),
+                    ..
+                }) => self.kinship(*mother, *father),
+                _ => 0.0,
+            };
+            return (1.0 + inbreeding) / 2.0;
+        }
+        let (younger, older) = if id1 > id2 { (id1, id2) } else { (id2, id1) };
+        match self.find(younger) {
+            Some(pokemon) => {
+                let via = |parent: Option<u32>| parent.map_or(0.0, |p| self.kinship(p, older));
+                (via(pokemon.mother) + via(pokemon.father)) / 2.0
+            }
+            None => 0.0,
+        }
+    }
+
+    // 0.5 entre frères et soeurs ou entre un parent et son petit, 0.25 entre
+    // demi-frères, 0.125 entre cousins
+    pub fn relatedness(&self, id1: u32, id2: u32) -> f32 {
+        if id1 == id2 {
+            return 1.0;
+        }
+        2.0 * self.kinship(id1, id2)
+    }
+
+    // Renvoie le coefficient si le couple est autorisé par les règles de l'élevage
+    pub fn check_relatedness(&self, id1: u32, id2: u32) -> Result<f32, BreedingRefusal> {
+        let relatedness = self.relatedness(id1, id2);
+        if self.inbreeding.exceeds(relatedness)
+            && self.inbreeding.policy == InbreedingPolicy::Refuse
+        {
+            return Err(BreedingRefusal::CloseRelatives(relatedness));
+        }
+        Ok(relatedness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pokedex::pokedex;
+    use crate::Gender;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Ajoute un Rattata à l'élevage avec les parents donnés
+    fn add(breeding: &mut Breeding, parents: Option<(u32, u32)>, rng: &mut StdRng) -> u32 {
+        let species = pokedex().find_by_name("Rattata").unwrap().id;
+        let mut pokemon = Pokemon::new(species, 5, Gender::Female, rng);
+        if let Some((mother, father)) = parents {
+            pokemon.mother = Some(mother);
+            pokemon.father = Some(father);
+        }
+        breeding.add_pokemon(pokemon)
+    }
+
+    #[test]
+    fn relatedness_coefficients() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut breeding = Breeding::new(0);
+        let [mother, father, other_father, in_law1, in_law2] =
+            [(); 5].map(|_| add(&mut breeding, None, &mut rng));
+        let sibling1 = add(&mut breeding, Some((mother, father)), &mut rng);
+        let sibling2 = add(&mut breeding, Some((mother, father)), &mut rng);
+        let half_sibling = add(&mut breeding, Some((mother, other_father)), &mut rng);
+        let cousin1 = add(&mut breeding, Some((sibling1, in_law1)), &mut rng);
+        let cousin2 = add(&mut breeding, Some((sibling2, in_law2)), &mut rng);
+
+        assert_eq!(breeding.relatedness(sibling1, sibling2), 0.5);
+        assert_eq!(breeding.relatedness(mother, sibling1), 0.5);
+        assert_eq!(breeding.relatedness(sibling1, half_sibling), 0.25);
+        assert_eq!(breeding.relatedness(cousin1, cousin2), 0.125);
+        assert_eq!(breeding.relatedness(mother, father), 0.0);
+    }
+
+    #[test]
+    fn pair_at_max_relatedness_is_accepted() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut breeding = Breeding::new(0);
+        let [mother, father, other_father] = [(); 3].map(|_| add(&mut breeding, None, &mut rng));
+        let sibling1 = add(&mut breeding, Some((mother, father)), &mut rng);
+        let sibling2 = add(&mut breeding, Some((mother, father)), &mut rng);
+        let half_sibling = add(&mut breeding, Some((mother, other_father)), &mut rng);
+
+        assert_eq!(breeding.inbreeding.max_relatedness, 0.25);
+        assert_eq!(breeding.check_relatedness(sibling1, half_sibling), Ok(0.25));
+        assert_eq!(
+            breeding.check_relatedness(sibling1, sibling2),
+            Err(BreedingRefusal::CloseRelatives(0.5))
+        );
+    }
+}
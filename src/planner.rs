@@ -0,0 +1,296 @@
+use crate::pokedex::pokedex;
+use crate::stats::{Nature, Stats};
+use crate::{Breeding, Gender, Pokemon, MIN_BREEDING_LEVEL};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fmt;
+
+const DEFAULT_SIMULATIONS: u32 = 200;
+const DEFAULT_MAX_GENERATIONS: u32 = 3;
+const DEFAULT_BEAM_WIDTH: usize = 4;
+// Un bébé intermédiaire doit pouvoir s'obtenir en une dizaine d'oeufs
+const KEEP_QUANTILE: f32 = 0.1;
+
+// Ce que l'éleveur cherche à obtenir. Un bébé naît toujours sous sa forme de
+// base : viser Reptincel revient à viser un Salamèche qu'il faudra entraîner.
+#[derive(Debug, Clone, Default)]
+pub struct BreedingTarget {
+    pub species: Option<u32>,
+    pub min_ivs: Stats,
+    pub nature: Option<Nature>,
+    pub move_name: Option<String>,
+}
+
+impl BreedingTarget {
+    // Part des critères remplis, entre 0 et 1, pour guider la recherche quand
+    // aucun couple ne peut encore atteindre la cible
+    fn score(&self, pokemon: &Pokemon) -> f32 {
+        let mut criteria = Vec::new();
+        if let Some(species) = self.species {
+            let matches = pokedex().base_form(species) == pokedex().base_form(pokemon.species);
+            criteria.push(if matches { 1.0 } else { 0.0 });
+        }
+        if let Some(nature) = &self.nature {
+            criteria.push(if &pokemon.nature == nature { 1.0 } else { 0.0 });
+        }
+        if let Some(move_name) = &self.move_name {
+            criteria.push(if pokemon.moves.contains(move_name) {
+                1.0
+            } else {
+                0.0
+            });
+        }
+        let ivs = [
+            (pokemon.ivs.hp, self.min_ivs.hp),
+            (pokemon.ivs.attack, self.min_ivs.attack),
+            (pokemon.ivs.defense, self.min_ivs.defense),
+            (pokemon.ivs.speed, self.min_ivs.speed),
+        ];
+        for (iv, min) in ivs {
+            if min > 0 {
+                criteria.push((iv.min(min) as f32) / min as f32);
+            }
+        }
+        if criteria.is_empty() {
+            return 1.0;
+        }
+        criteria.iter().sum::<f32>() / criteria.len() as f32
+    }
+
+    fn is_met(&self, pokemon: &Pokemon) -> bool {
+        self.score(pokemon) >= 1.0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PlanStep {
+    pub generation: u32,
+    pub mother: u32,
+    pub father: u32,
+    pub expected_eggs: f32,
+    // Bébé intermédiaire à garder pour la génération suivante, absent à la
+    // dernière étape
+    pub keep: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BreedingPlan {
+    pub steps: Vec<PlanStep>,
+    pub expected_eggs: f32,
+}
+
+impl fmt::Display for BreedingPlan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.steps.is_empty() {
+            return write!(f, "La cible est déjà présente dans l'élevage.");
+        }
+        for step in &self.steps {
+            write!(
+                f,
+                "Génération {} : croiser n°{} et n°{} (environ {:.1} oeufs)",
+                step.generation, step.mother, step.father, step.expected_eggs
+            )?;
+            match &step.keep {
+                Some(description) => writeln!(f, ", garder {}", description)?,
+                None => writeln!(f, ", la cible est obtenue")?,
+            }
+        }
+        write!(f, "Total attendu : {:.1} oeufs", self.expected_eggs)
+    }
+}
+
+struct Candidate {
+    breeding: Breeding,
+    steps: Vec<PlanStep>,
+    expected_eggs: f32,
+    score: f32,
+    // Le bébé gardé à la génération précédente doit servir à la suivante
+    newest: Option<u32>,
+}
+
+// Recherche en faisceau : à chaque génération on simule tous les couples
+// possibles, on garde les meilleurs bébés comme nouveaux reproducteurs et on
+// s'arrête à la première génération où la cible devient atteignable.
+pub struct Planner {
+    pub simulations: u32,
+    pub max_generations: u32,
+    pub beam_width: usize,
+    rng: StdRng,
+}
+
+impl Planner {
+    pub fn new(seed: u64) -> Self {
+        Planner {
+            simulations: DEFAULT_SIMULATIONS,
+            max_generations: DEFAULT_MAX_GENERATIONS,
+            beam_width: DEFAULT_BEAM_WIDTH,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn plan(&mut self, breeding: &Breeding, target: &BreedingTarget) -> Option<BreedingPlan> {
+        // Sans simulation, aucun couple ne peut être évalué
+        if self.simulations == 0 {
+            return None;
+        }
+        if breeding.pokemon_list.iter().any(|p| target.is_met(p)) {
+            return Some(BreedingPlan {
+                steps: Vec::new(),
+                expected_eggs: 0.0,
+            });
+        }
+
+        let mut beam = vec![Candidate {
+            breeding: breeding.clone(),
+            steps: Vec::new(),
+            expected_eggs: 0.0,
+            score: 0.0,
+            newest: None,
+        }];
+        for generation in 1..=self.max_generations {
+            let mut best: Option<BreedingPlan> = None;
+            let mut next_beam = Vec::new();
+            for candidate in &beam {
+                let pairs =
+                    Planner::pairs(&candidate.breeding)
+                        .into_iter()
+                        .filter(|&(mother, father)| {
+                            candidate
+                                .newest
+                                .is_none_or(|id| id == mother || id == father)
+                        });
+                for (mother, father) in pairs {
+                    let Some((successes, best_baby)) =
+                        self.simulate(&candidate.breeding, mother, father, target)
+                    else {
+                        continue;
+                    };
+                    let mut steps = candidate.steps.clone();
+                    if successes > 0 {
+                        let expected_eggs = self.simulations as f32 / successes as f32;
+                        steps.push(PlanStep {
+                            generation,
+                            mother,
+                            father,
+                            expected_eggs,
+                            keep: None,
+                        });
+                        let total = candidate.expected_eggs + expected_eggs;
+                        if best.as_ref().is_none_or(|plan| total < plan.expected_eggs) {
+                            best = Some(BreedingPlan {
+                                steps,
+                                expected_eggs: total,
+                            });
+                        }
+                    } else if let Some((baby, probability)) = best_baby {
+                        let expected_eggs = 1.0 / probability;
+                        let score = target.score(&baby);
+                        steps.push(PlanStep {
+                            generation,
+                            mother,
+                            father,
+                            expected_eggs,
+                            keep: Some(Planner::describe(&baby)),
+                        });
+                        let mut breeding = candidate.breeding.clone();
+                        let id = breeding.add_pokemon(baby);
+                        if let Some(PlanStep {
+                            keep: Some(keep), ..
+                        }) = steps.last_mut()
+                        {
+                            *keep = format!("n°{} : {}", id, keep);
+                        }
+                        next_beam.push(Candidate {
+                            breeding,
+                            steps,
+                            expected_eggs: candidate.expected_eggs + expected_eggs,
+                            score,
+                            newest: Some(id),
+                        });
+                    }
+                }
+            }
+            if best.is_some() {
+                return best;
+            }
+            next_beam.sort_by(|a, b| {
+                b.score
+                    .total_cmp(&a.score)
+                    .then(a.expected_eggs.total_cmp(&b.expected_eggs))
+            });
+            next_beam.truncate(self.beam_width);
+            if next_beam.is_empty() {
+                return None;
+            }
+            beam = next_beam;
+        }
+        None
+    }
+
+    fn pairs(breeding: &Breeding) -> Vec<(u32, u32)> {
+        let list = &breeding.pokemon_list;
+        let mut pairs = Vec::new();
+        for (i, first) in list.iter().enumerate() {
+            for second in &list[i + 1..] {
+                if first.can_breed(second).is_ok()
+                    && breeding.check_relatedness(first.id, second.id).is_ok()
+                {
+                    let (mother, father) = Pokemon::parents(first, second);
+                    pairs.push((mother.id, father.id));
+                }
+            }
+        }
+        pairs
+    }
+
+    // Nombre de bébés simulés qui atteignent la cible, et sinon un bébé parmi
+    // les meilleurs avec la probabilité d'en obtenir un aussi bon
+    fn simulate(
+        &mut self,
+        breeding: &Breeding,
+        mother: u32,
+        father: u32,
+        target: &BreedingTarget,
+    ) -> Option<(u32, Option<(Pokemon, f32)>)> {
+        // Même chemin que Breeding::attempt_breeding, pénalité de consanguinité comprise
+        let mut babies = Vec::new();
+        for _ in 0..self.simulations {
+            babies.push(breeding.conceive(mother, father, &mut self.rng).ok()?);
+        }
+        let successes = babies.iter().filter(|b| target.is_met(b)).count() as u32;
+        if successes > 0 {
+            return Some((successes, None));
+        }
+
+        babies.sort_by(|a, b| target.score(b).total_cmp(&target.score(a)));
+        let rank = (babies.len() as f32 * KEEP_QUANTILE) as usize;
+        let mut best_baby = babies.get(rank.min(babies.len() - 1))?.clone();
+        let threshold = target.score(&best_baby);
+        let as_good = babies
+            .iter()
+            .filter(|b| target.score(b) >= threshold)
+            .count();
+        let probability = as_good as f32 / babies.len() as f32;
+        // Le bébé sera entraîné jusqu'à pouvoir se reproduire à son tour
+        best_baby.level = MIN_BREEDING_LEVEL;
+        best_baby.update_stats();
+        Some((0, Some((best_baby, probability))))
+    }
+
+    fn describe(baby: &Pokemon) -> String {
+        format!(
+            "un {} {} de nature {} (IV {}/{}/{}/{})",
+            baby.species().name_fr,
+            match baby.gender {
+                Gender::Male => "mâle",
+                Gender::Female => "femelle",
+                Gender::Genderless => "asexué",
+            },
+            baby.nature,
+            baby.ivs.hp,
+            baby.ivs.attack,
+            baby.ivs.defense,
+            baby.ivs.speed
+        )
+    }
+}
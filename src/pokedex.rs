@@ -0,0 +1,155 @@
+use crate::growth::GrowthRate;
+use crate::stats::Stats;
+use crate::{Gender, PokemonType};
+use rand::Rng;
+use serde::Deserialize;
+use std::fmt;
+use std::sync::OnceLock;
+
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+pub enum EggGroup {
+    Monster,
+    Water1,
+    Water2,
+    Water3,
+    Bug,
+    Flying,
+    Field,
+    Fairy,
+    Grass,
+    HumanLike,
+    Mineral,
+    Amorphous,
+    Dragon,
+    Ditto,
+    Undiscovered,
+}
+
+impl fmt::Display for EggGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            EggGroup::Monster => "Monstrueux",
+            EggGroup::Water1 => "Aquatique 1",
+            EggGroup::Water2 => "Aquatique 2",
+            EggGroup::Water3 => "Aquatique 3",
+            EggGroup::Bug => "Insectoïde",
+            EggGroup::Flying => "Aérien",
+            EggGroup::Field => "Terrestre",
+            EggGroup::Fairy => "Féerique",
+            EggGroup::Grass => "Végétal",
+            EggGroup::HumanLike => "Humanoïde",
+            EggGroup::Mineral => "Minéral",
+            EggGroup::Amorphous => "Amorphe",
+            EggGroup::Dragon => "Draconique",
+            EggGroup::Ditto => "Métamorph",
+            EggGroup::Undiscovered => "Inconnu",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Evolution {
+    pub level: u32,
+    pub into: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Species {
+    pub id: u32,
+    pub name_fr: String,
+    pub name_en: String,
+    pub types: Vec<PokemonType>,
+    pub base_stats: Stats,
+    pub female_ratio: Option<f32>,
+    pub egg_groups: Vec<EggGroup>,
+    pub egg_cycles: u32,
+    pub growth_rate: GrowthRate,
+    // De 3 (légendaires) à 255 (Pokémons communs)
+    pub catch_rate: u32,
+    #[serde(default)]
+    pub evolution: Option<Evolution>,
+}
+
+impl Species {
+    pub fn random_gender<R: Rng>(&self, rng: &mut R) -> Gender {
+        match self.female_ratio {
+            None => Gender::Genderless,
+            Some(ratio) if rng.gen::<f32>() < ratio => Gender::Female,
+            Some(_) => Gender::Male,
+        }
+    }
+
+    pub fn is_genderless(&self) -> bool {
+        self.female_ratio.is_none()
+    }
+
+    pub fn in_egg_group(&self, group: &EggGroup) -> bool {
+        self.egg_groups.contains(group)
+    }
+
+    pub fn shares_egg_group(&self, other: &Species) -> bool {
+        self.egg_groups.iter().any(|g| other.in_egg_group(g))
+    }
+}
+
+impl fmt::Display for Species {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let types: Vec<String> = self.types.iter().map(|t| t.to_string()).collect();
+        let egg_groups: Vec<String> = self.egg_groups.iter().map(|g| g.to_string()).collect();
+        write!(
+            f,
+            "#{:03} {} ({}) - {} - Groupes Oeuf: {} - ",
+            self.id,
+            self.name_fr,
+            self.name_en,
+            types.join("/"),
+            egg_groups.join(", ")
+        )?;
+        match self.female_ratio {
+            Some(ratio) => write!(f, "{}% de femelles", ratio * 100.0),
+            None => write!(f, "asexué"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Pokedex {
+    species: Vec<Species>,
+}
+
+impl Pokedex {
+    pub fn all(&self) -> &[Species] {
+        &self.species
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Species> {
+        self.species.iter().find(|s| s.id == id)
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<&Species> {
+        self.species.iter().find(|s| {
+            s.name_fr.to_lowercase() == name.to_lowercase()
+                || s.name_en.to_lowercase() == name.to_lowercase()
+        })
+    }
+
+    // Remonte la chaîne d'évolution jusqu'au premier stade, celui qui sort de l'oeuf
+    pub fn base_form(&self, id: u32) -> u32 {
+        match self
+            .species
+            .iter()
+            .find(|s| s.evolution.as_ref().is_some_and(|e| e.into == id))
+        {
+            Some(previous) => self.base_form(previous.id),
+            None => id,
+        }
+    }
+}
+
+pub fn pokedex() -> &'static Pokedex {
+    static POKEDEX: OnceLock<Pokedex> = OnceLock::new();
+    POKEDEX.get_or_init(|| {
+        serde_json::from_str(include_str!("../pokedex.json")).expect("pokedex.json invalide")
+    })
+}
@@ -1,115 +1,604 @@
+mod ai;
+mod battle;
+mod capture;
+mod egg;
+mod encounter;
+mod genetics;
+mod growth;
+mod migration;
+mod moves;
+mod pedigree;
+mod planner;
+mod pokedex;
+mod query;
+mod shell;
+mod simulation;
+mod stats;
+mod status;
+mod storage;
+mod trainer;
+
+use ai::{Difficulty, Opponent};
+use battle::{BattleOutcome, Side};
+use capture::{catch_probability, Ball, CaptureResult};
+use egg::Egg;
+use encounter::zones;
+use pedigree::{InbreedingPolicy, InbreedingRules};
+use planner::{BreedingTarget, Planner};
+use pokedex::{pokedex, EggGroup, Species};
+use query::Query;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
+use shell::Shell;
+use simulation::{ReleasePolicy, SimulationConfig};
+use stats::{Nature, Stats};
+use status::StatusCondition;
 use std::fmt;
-use std::fs;
+use std::io;
+use std::path::Path;
+use storage::{invalid_data, Restoration};
+use trainer::Trainer;
+
+const MIN_BREEDING_LEVEL: u32 = 5;
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 enum PokemonType {
+    Normal,
     Fire,
     Water,
     Grass,
     Electric,
+    Ice,
+    Fighting,
+    Poison,
+    Ground,
+    Flying,
+    Psychic,
+    Bug,
+    Rock,
+    Ghost,
+    Dragon,
+    Dark,
+    Steel,
+    Fairy,
+}
+
+impl PokemonType {
+    const ALL: [PokemonType; 18] = [
+        PokemonType::Normal,
+        PokemonType::Fire,
+        PokemonType::Water,
+        PokemonType::Grass,
+        PokemonType::Electric,
+        PokemonType::Ice,
+        PokemonType::Fighting,
+        PokemonType::Poison,
+        PokemonType::Ground,
+        PokemonType::Flying,
+        PokemonType::Psychic,
+        PokemonType::Bug,
+        PokemonType::Rock,
+        PokemonType::Ghost,
+        PokemonType::Dragon,
+        PokemonType::Dark,
+        PokemonType::Steel,
+        PokemonType::Fairy,
+    ];
+
+    fn from_name(name: &str) -> Option<PokemonType> {
+        find_by_name(&PokemonType::ALL, name)
+    }
+}
+
+// Accepte le nom anglais de la variante (Fire) comme le nom français affiché
+// (Feu), sans tenir compte de la casse
+fn find_by_name<T: fmt::Debug + fmt::Display + Clone>(all: &[T], name: &str) -> Option<T> {
+    all.iter()
+        .find(|value| {
+            format!("{:?}", value).eq_ignore_ascii_case(name)
+                || value.to_string().to_lowercase() == name.to_lowercase()
+        })
+        .cloned()
+}
+
+impl fmt::Display for PokemonType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            PokemonType::Normal => "Normal",
+            PokemonType::Fire => "Feu",
+            PokemonType::Water => "Eau",
+            PokemonType::Grass => "Plante",
+            PokemonType::Electric => "Electrik",
+            PokemonType::Ice => "Glace",
+            PokemonType::Fighting => "Combat",
+            PokemonType::Poison => "Poison",
+            PokemonType::Ground => "Sol",
+            PokemonType::Flying => "Vol",
+            PokemonType::Psychic => "Psy",
+            PokemonType::Bug => "Insecte",
+            PokemonType::Rock => "Roche",
+            PokemonType::Ghost => "Spectre",
+            PokemonType::Dragon => "Dragon",
+            PokemonType::Dark => "Ténèbres",
+            PokemonType::Steel => "Acier",
+            PokemonType::Fairy => "Fée",
+        };
+        write!(f, "{}", name)
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 enum Gender {
     Male,
     Female,
+    Genderless,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum BreedingRefusal {
+    UnknownPokemon(u32),
+    SamePokemon,
+    TooYoung(String),
+    Undiscovered(String),
+    TwoDittos,
+    Genderless(String),
+    SameGender,
+    NoCommonEggGroup,
+    CloseRelatives(f32),
+}
+
+impl fmt::Display for BreedingRefusal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BreedingRefusal::UnknownPokemon(id) => {
+                write!(f, "aucun Pokémon avec l'identifiant {}", id)
+            }
+            BreedingRefusal::SamePokemon => {
+                write!(f, "un Pokémon ne peut pas se reproduire avec lui-même")
+            }
+            BreedingRefusal::TooYoung(name) => write!(
+                f,
+                "{} doit être au moins niveau {}",
+                name, MIN_BREEDING_LEVEL
+            ),
+            BreedingRefusal::Undiscovered(name) => {
+                write!(f, "{} appartient au groupe Oeuf Inconnu", name)
+            }
+            BreedingRefusal::TwoDittos => {
+                write!(f, "deux Métamorph ne peuvent pas se reproduire ensemble")
+            }
+            BreedingRefusal::Genderless(name) => write!(
+                f,
+                "{} est asexué et ne peut se reproduire qu'avec un Métamorph",
+                name
+            ),
+            BreedingRefusal::SameGender => write!(f, "les deux Pokémons sont du même sexe"),
+            BreedingRefusal::NoCommonEggGroup => {
+                write!(f, "les deux Pokémons n'ont aucun groupe Oeuf en commun")
+            }
+            BreedingRefusal::CloseRelatives(relatedness) => write!(
+                f,
+                "les deux Pokémons sont trop proches parents (parenté de {:.1} %)",
+                relatedness * 100.0
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum PokemonEvent {
+    LevelUp { name: String, level: u32 },
+    LearnedMove { name: String, move_name: String },
+    Evolved { from: String, into: String },
+    Hatched { name: String },
+}
+
+impl fmt::Display for PokemonEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PokemonEvent::LevelUp { name, level } => {
+                write!(f, "{} monte au niveau {} !", name, level)
+            }
+            PokemonEvent::LearnedMove { name, move_name } => {
+                write!(f, "{} apprend {} !", name, move_name)
+            }
+            PokemonEvent::Evolved { from, into } => write!(f, "{} évolue en {} !", from, into),
+            PokemonEvent::Hatched { name } => write!(f, "{} sort de son oeuf !", name),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 struct Pokemon {
-    name: String,
+    #[serde(default)]
+    id: u32,
+    species: u32,
+    #[serde(default)]
+    nickname: Option<String>,
     level: u32,
     pokemon_type: PokemonType,
+    #[serde(default)]
+    secondary_type: Option<PokemonType>,
     xp: u32,
     gender: Gender,
+    #[serde(default)]
+    ivs: Stats,
+    #[serde(default)]
+    nature: Nature,
+    #[serde(default)]
+    stats: Stats,
+    #[serde(default)]
+    moves: Vec<String>,
+    #[serde(default)]
+    mother: Option<u32>,
+    #[serde(default)]
+    father: Option<u32>,
+    #[serde(default)]
+    generation: u32,
+    // PV actuels, ramenés aux PV max au chargement ; absents des anciennes
+    // sauvegardes, le Pokémon est alors en pleine forme
+    #[serde(default = "full_health")]
+    hp: u32,
+    #[serde(default)]
+    status: Option<StatusCondition>,
+}
+
+fn full_health() -> u32 {
+    u32::MAX
 }
 
 impl Pokemon {
-    fn new(name: &str, level: u32, pokemon_type: PokemonType, gender: Gender) -> Self {
-        Pokemon {
-            name: name.to_string(),
+    fn new<R: Rng>(species: u32, level: u32, gender: Gender, rng: &mut R) -> Self {
+        let gender = if Pokemon::species_info(species).is_genderless() {
+            Gender::Genderless
+        } else {
+            gender
+        };
+        let mut pokemon = Pokemon {
+            id: 0,
+            species,
+            nickname: None,
             level,
-            pokemon_type,
+            pokemon_type: Pokemon::species_info(species).types[0].clone(),
+            secondary_type: None,
             xp: 0,
             gender,
+            ivs: Stats::random_ivs(rng),
+            nature: Nature::random(rng),
+            stats: Stats::default(),
+            moves: Vec::new(),
+            mother: None,
+            father: None,
+            generation: 0,
+            hp: full_health(),
+            status: None,
+        };
+        pokemon.update_types();
+        pokemon.update_stats();
+        pokemon.learn_initial_moves();
+        pokemon
+    }
+
+    fn species_info(species: u32) -> &'static Species {
+        pokedex()
+            .get(species)
+            .unwrap_or_else(|| panic!("Espèce inconnue dans le Pokédex: {}", species))
+    }
+
+    fn species(&self) -> &'static Species {
+        Pokemon::species_info(self.species)
+    }
+
+    fn name(&self) -> &str {
+        match &self.nickname {
+            Some(nickname) => nickname,
+            None => &self.species().name_fr,
+        }
+    }
+
+    fn set_nickname(&mut self, nickname: &str) {
+        self.nickname = Some(nickname.to_string());
+    }
+
+    fn learn_initial_moves(&mut self) {
+        for name in moves::catalog().learned_up_to(self.species, self.level) {
+            self.learn_move(name);
+        }
+    }
+
+    fn learn_move(&mut self, name: &str) -> bool {
+        if self.moves.iter().any(|m| m == name) {
+            return false;
+        }
+        if self.moves.len() == moves::MAX_KNOWN_MOVES {
+            self.moves.remove(0);
+        }
+        self.moves.push(name.to_string());
+        true
+    }
+
+    fn update_types(&mut self) {
+        let types = &self.species().types;
+        self.pokemon_type = types[0].clone();
+        self.secondary_type = types.get(1).cloned();
+    }
+
+    // Les PV perdus le restent quand les PV max changent (montée de niveau,
+    // évolution), et un Pokémon K.O. reste K.O. Sans PV max connus (Pokémon
+    // neuf, ancienne sauvegarde), les PV actuels sont simplement plafonnés.
+    fn update_stats(&mut self) {
+        let previous_max = self.stats.hp;
+        self.stats = Stats::compute(
+            &self.species().base_stats,
+            &self.ivs,
+            self.level,
+            &self.nature,
+        );
+        if previous_max == 0 {
+            self.hp = self.hp.min(self.stats.hp);
+        } else if !self.is_fainted() {
+            let damage = previous_max.saturating_sub(self.hp);
+            self.hp = self.stats.hp.saturating_sub(damage).max(1);
+        }
+    }
+
+    // L'XP stockée est celle accumulée depuis le dernier niveau, une grosse
+    // quantité peut faire gagner plusieurs niveaux d'un coup
+    fn gain_xp(&mut self, amount: u32) -> Vec<PokemonEvent> {
+        let mut events = Vec::new();
+        if self.level >= growth::MAX_LEVEL {
+            return events;
         }
+        // L'XP au-delà de ce qu'il faut pour atteindre le niveau maximum est perdue
+        let growth_rate = &self.species().growth_rate;
+        let remaining = (self.level..growth::MAX_LEVEL)
+            .map(|level| growth_rate.xp_between_levels(level))
+            .fold(0u32, u32::saturating_add)
+            .saturating_sub(self.xp);
+        self.xp = self.xp.saturating_add(amount.min(remaining));
+        while self.level < growth::MAX_LEVEL {
+            let needed = self.species().growth_rate.xp_between_levels(self.level);
+            if self.xp < needed {
+                break;
+            }
+            self.xp -= needed;
+            events.extend(self.level_up());
+        }
+        if self.level >= growth::MAX_LEVEL {
+            self.xp = 0;
+        }
+        events
     }
 
-    fn gain_xp(&mut self, amount: u32) {
-        self.xp += amount;
-        while self.xp >= 100 {
-            self.xp -= 100;
-            self.level_up();
+    fn xp_to_next_level(&self) -> Option<u32> {
+        if self.level >= growth::MAX_LEVEL {
+            return None;
         }
+        let needed = self.species().growth_rate.xp_between_levels(self.level);
+        Some(needed.saturating_sub(self.xp))
     }
 
-    fn level_up(&mut self) {
+    fn level_up(&mut self) -> Vec<PokemonEvent> {
         self.level += 1;
+        let mut events = vec![PokemonEvent::LevelUp {
+            name: self.name().to_string(),
+            level: self.level,
+        }];
+
+        if let Some(evolution) = &self.species().evolution {
+            if self.level >= evolution.level {
+                let into = Pokemon::species_info(evolution.into);
+                events.push(PokemonEvent::Evolved {
+                    from: self.name().to_string(),
+                    into: into.name_fr.clone(),
+                });
+                self.species = into.id;
+                self.update_types();
+            }
+        }
+
+        self.update_stats();
+        for move_name in moves::catalog().learned_at(self.species, self.level) {
+            if self.learn_move(move_name) {
+                events.push(PokemonEvent::LearnedMove {
+                    name: self.name().to_string(),
+                    move_name: move_name.to_string(),
+                });
+            }
+        }
+        events
     }
 
-    fn can_breed(&self, other: &Pokemon) -> bool {
-        self.pokemon_type == other.pokemon_type
-            && self.gender != other.gender
-            && self.level >= 5
-            && other.level >= 5
+    fn has_type(&self, pokemon_type: &PokemonType) -> bool {
+        self.pokemon_type == *pokemon_type || self.secondary_type.as_ref() == Some(pokemon_type)
     }
 
-    fn breed(pokemon1: &Pokemon, pokemon2: &Pokemon) -> Option<Pokemon> {
-        if pokemon1.can_breed(pokemon2) {
-            Some(Pokemon::new(
-                "Mystere",
-                1,
-                pokemon1.pokemon_type.clone(),
-                if rand::random() {
-                    Gender::Male
-                } else {
-                    Gender::Female
-                },
-            ))
+    fn is_ditto(&self) -> bool {
+        self.species().in_egg_group(&EggGroup::Ditto)
+    }
+
+    fn can_breed(&self, other: &Pokemon) -> Result<(), BreedingRefusal> {
+        for pokemon in [self, other] {
+            if pokemon.level < MIN_BREEDING_LEVEL {
+                return Err(BreedingRefusal::TooYoung(pokemon.name().to_string()));
+            }
+            if pokemon.species().in_egg_group(&EggGroup::Undiscovered) {
+                return Err(BreedingRefusal::Undiscovered(pokemon.name().to_string()));
+            }
+        }
+
+        // Métamorph peut se reproduire avec n'importe quel Pokémon, même asexué
+        match (self.is_ditto(), other.is_ditto()) {
+            (true, true) => return Err(BreedingRefusal::TwoDittos),
+            (true, false) | (false, true) => return Ok(()),
+            (false, false) => {}
+        }
+
+        for pokemon in [self, other] {
+            if pokemon.gender == Gender::Genderless {
+                return Err(BreedingRefusal::Genderless(pokemon.name().to_string()));
+            }
+        }
+        if self.gender == other.gender {
+            return Err(BreedingRefusal::SameGender);
+        }
+        if !self.species().shares_egg_group(other.species()) {
+            return Err(BreedingRefusal::NoCommonEggGroup);
+        }
+        Ok(())
+    }
+
+    // L'espèce du bébé est celle de la mère, ou du partenaire de Métamorph
+    fn parents<'a>(pokemon1: &'a Pokemon, pokemon2: &'a Pokemon) -> (&'a Pokemon, &'a Pokemon) {
+        if pokemon1.is_ditto() || (!pokemon2.is_ditto() && pokemon2.gender == Gender::Female) {
+            (pokemon2, pokemon1)
         } else {
-            None
+            (pokemon1, pokemon2)
+        }
+    }
+
+    fn breed<R: Rng>(
+        pokemon1: &Pokemon,
+        pokemon2: &Pokemon,
+        rng: &mut R,
+    ) -> Result<Pokemon, BreedingRefusal> {
+        pokemon1.can_breed(pokemon2)?;
+        let (mother, father) = Pokemon::parents(pokemon1, pokemon2);
+        let species = pokedex().base_form(mother.species);
+        let gender = Pokemon::species_info(species).random_gender(rng);
+
+        let mut baby = Pokemon::new(species, 1, gender, rng);
+        baby.ivs = genetics::crossover_ivs(&mother.ivs, &father.ivs, rng);
+        baby.nature = genetics::inherit_nature(&mother.nature, &father.nature, rng);
+        baby.update_stats();
+        for move_name in genetics::inherited_moves(mother, father, species) {
+            baby.learn_move(move_name);
         }
+        // Un parent qui n'appartient à aucun élevage n'a pas encore d'identifiant
+        baby.mother = Some(mother.id).filter(|&id| id != 0);
+        baby.father = Some(father.id).filter(|&id| id != 0);
+        baby.generation = mother.generation.max(father.generation) + 1;
+        Ok(baby)
     }
 }
 
 impl fmt::Display for Pokemon {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Un Pokémon sauvage n'a pas encore d'identifiant dans l'élevage
+        if self.id != 0 {
+            write!(f, "{}. ", self.id)?;
+        }
         write!(
             f,
-            "{} (Niveau {} - {} - XP: {} - {})",
-            self.name,
+            "{} (Niveau {} - {} - XP: {} - {} - {} - PV: {} Att: {} Déf: {} Vit: {})",
+            match &self.nickname {
+                Some(nickname) => format!("{} [{}]", nickname, self.species().name_fr),
+                None => self.species().name_fr.clone(),
+            },
             self.level,
-            match self.pokemon_type {
-                PokemonType::Fire => "Feu",
-                PokemonType::Water => "Eau",
-                PokemonType::Grass => "Plante",
-                PokemonType::Electric => "Electrik",
+            match &self.secondary_type {
+                Some(secondary_type) => format!("{}/{}", self.pokemon_type, secondary_type),
+                None => self.pokemon_type.to_string(),
+            },
+            match self.xp_to_next_level() {
+                Some(remaining) => format!("{}/{}", self.xp, self.xp + remaining),
+                None => "max".to_string(),
             },
-            self.xp,
             match self.gender {
                 Gender::Male => "Male",
                 Gender::Female => "Femelle",
-            }
-        )
+                Gender::Genderless => "Asexué",
+            },
+            self.nature,
+            if self.hp < self.stats.hp {
+                format!("{}/{}", self.hp, self.stats.hp)
+            } else {
+                self.stats.hp.to_string()
+            },
+            self.stats.attack,
+            self.stats.defense,
+            self.stats.speed
+        )?;
+        if !self.moves.is_empty() {
+            write!(f, " [{}]", self.moves.join(", "))?;
+        }
+        if self.is_fainted() {
+            write!(f, " K.O.")?;
+        } else if let Some(status) = self.status {
+            write!(f, " {}", status)?;
+        }
+        Ok(())
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 struct Breeding {
     pokemon_list: Vec<Pokemon>,
+    #[serde(default)]
+    eggs: Vec<Egg>,
+    #[serde(default)]
+    last_id: u32,
+    #[serde(default)]
+    inbreeding: InbreedingRules,
+    // Graine de départ de la session : rejouer les mêmes actions depuis le
+    // début avec cette graine donne exactement les mêmes résultats
+    #[serde(default)]
+    seed: u64,
+    // Graine de la suite du tirage, renouvelée à chaque sauvegarde pour qu'une
+    // partie rechargée poursuive le tirage au lieu de le rejouer depuis le début
+    #[serde(default)]
+    resume_seed: Option<u64>,
+    #[serde(default)]
+    trainer: Trainer,
 }
 
 impl Breeding {
-    fn save_to_file(&self, filename: &str) -> std::io::Result<()> {
-        let json = serde_json::to_string_pretty(self)?;
-        fs::write(filename, json)
+    fn save_to_file(&self, filename: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut save = serde_json::to_value(self)?;
+        migration::stamp(&mut save);
+        storage::write_atomic(filename.as_ref(), save)
+    }
+
+    // Indique aussi la sauvegarde précédente utilisée si le fichier était illisible
+    fn load_from_file(filename: impl AsRef<Path>) -> io::Result<(Self, Option<Restoration>)> {
+        let (mut save, restoration) = storage::read_with_fallback(filename.as_ref())?;
+        migration::migrate(&mut save)?;
+        let mut breeding: Breeding = serde_json::from_value(save)?;
+        let unhatched = breeding.eggs.iter_mut().map(|egg| &mut egg.pokemon);
+        for pokemon in breeding.pokemon_list.iter_mut().chain(unhatched) {
+            if pokedex().get(pokemon.species).is_none() {
+                return Err(invalid_data(format!(
+                    "Espèce inconnue dans le Pokédex: {}",
+                    pokemon.species
+                )));
+            }
+            pokemon.update_types();
+            pokemon.update_stats();
+            if pokemon.moves.is_empty() {
+                pokemon.learn_initial_moves();
+            }
+        }
+
+        // Les anciennes sauvegardes n'ont pas d'identifiants, on leur en attribue
+        let max_id = breeding.all_pokemon().map(|p| p.id).max().unwrap_or(0);
+        breeding.last_id = breeding.last_id.max(max_id);
+        let mut last_id = breeding.last_id;
+        let unhatched = breeding.eggs.iter_mut().map(|egg| &mut egg.pokemon);
+        for pokemon in breeding.pokemon_list.iter_mut().chain(unhatched) {
+            if pokemon.id == 0 {
+                last_id += 1;
+                pokemon.id = last_id;
+            }
+        }
+        breeding.last_id = last_id;
+        breeding.sync_trainer();
+        Ok((breeding, restoration))
     }
 
-    fn load_from_file(filename: &str) -> std::io::Result<Self> {
-        let content = fs::read_to_string(filename)?;
-        let breeding: Breeding = serde_json::from_str(&content)?;
-        Ok(breeding)
+    fn all_pokemon(&self) -> impl Iterator<Item = &Pokemon> {
+        let unhatched = self.eggs.iter().map(|egg| &egg.pokemon);
+        self.pokemon_list.iter().chain(unhatched)
     }
 
     fn filter_by_level(&self, min_level: u32) -> Vec<&Pokemon> {
@@ -122,84 +611,562 @@ impl Breeding {
     fn filter_by_type(&self, pokemon_type: &PokemonType) -> Vec<&Pokemon> {
         self.pokemon_list
             .iter()
-            .filter(|p| p.pokemon_type == *pokemon_type)
+            .filter(|p| p.has_type(pokemon_type))
             .collect()
     }
 
-    fn new() -> Self {
+    fn new(seed: u64) -> Self {
         Breeding {
             pokemon_list: Vec::new(),
+            eggs: Vec::new(),
+            last_id: 0,
+            inbreeding: InbreedingRules::default(),
+            seed,
+            resume_seed: None,
+            trainer: Trainer::default(),
         }
     }
 
-    fn add_pokemon(&mut self, pokemon: Pokemon) {
+    // Générateur au point où en est l'élevage : le début de la session, ou la
+    // suite tirée lors de la dernière sauvegarde
+    fn session_rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.resume_seed.unwrap_or(self.seed))
+    }
+
+    // Tire la graine de la suite de la session depuis le générateur en cours :
+    // continuer après la sauvegarde ou recharger celle-ci donne le même tirage
+    fn reseed<R: Rng>(&mut self, rng: &mut R) -> StdRng {
+        self.resume_seed = Some(rng.gen());
+        self.session_rng()
+    }
+
+    fn allocate_id(&mut self) -> u32 {
+        self.last_id += 1;
+        self.last_id
+    }
+
+    fn add_pokemon(&mut self, mut pokemon: Pokemon) -> u32 {
+        pokemon.id = self.allocate_id();
+        self.trainer.place(pokemon.id);
         self.pokemon_list.push(pokemon);
+        self.last_id
+    }
+
+    fn get(&self, id: u32) -> Option<&Pokemon> {
+        self.pokemon_list.iter().find(|p| p.id == id)
+    }
+
+    fn get_mut(&mut self, id: u32) -> Option<&mut Pokemon> {
+        self.pokemon_list.iter_mut().find(|p| p.id == id)
+    }
+
+    fn remove_pokemon(&mut self, id: u32) -> Option<Pokemon> {
+        let position = self.pokemon_list.iter().position(|p| p.id == id)?;
+        self.trainer.forget(id);
+        Some(self.pokemon_list.remove(position))
     }
 
     fn display_all(&self) {
         for pokemon in &self.pokemon_list {
             println!("{}", pokemon);
         }
+        for egg in &self.eggs {
+            println!("{}", egg);
+        }
     }
 
-    fn train_all(&mut self, xp_amount: u32) {
+    fn train_all(&mut self, xp_amount: u32) -> Vec<PokemonEvent> {
+        let mut events = Vec::new();
         for pokemon in &mut self.pokemon_list {
-            pokemon.gain_xp(xp_amount);
+            events.extend(pokemon.gain_xp(xp_amount));
         }
+        events.extend(self.walk(xp_amount));
+        events
     }
 
-    fn attempt_breeding(&mut self, index1: usize, index2: usize) -> Option<Pokemon> {
-        if index1 < self.pokemon_list.len() && index2 < self.pokemon_list.len() {
-            let pokemon1 = &self.pokemon_list[index1];
-            let pokemon2 = &self.pokemon_list[index2];
-            Pokemon::breed(pokemon1, pokemon2)
-        } else {
-            None
+    fn train(&mut self, id: u32, xp_amount: u32) -> Option<Vec<PokemonEvent>> {
+        let events = self.get_mut(id)?.gain_xp(xp_amount);
+        Some(events)
+    }
+
+    // Fait avancer l'incubation des oeufs, ceux qui éclosent rejoignent l'élevage
+    fn walk(&mut self, steps: u32) -> Vec<PokemonEvent> {
+        let mut events = Vec::new();
+        for mut egg in std::mem::take(&mut self.eggs) {
+            if egg.advance(steps) {
+                events.push(PokemonEvent::Hatched {
+                    name: egg.pokemon.name().to_string(),
+                });
+                self.trainer.place(egg.pokemon.id);
+                self.pokemon_list.push(egg.pokemon);
+            } else {
+                self.eggs.push(egg);
+            }
+        }
+        events
+    }
+
+    // Le bébé qu'un couple de l'élevage donnerait, règles de consanguinité
+    // comprises, sans encore l'ajouter aux oeufs
+    fn conceive<R: Rng>(
+        &self,
+        id1: u32,
+        id2: u32,
+        rng: &mut R,
+    ) -> Result<Pokemon, BreedingRefusal> {
+        if id1 == id2 {
+            return Err(BreedingRefusal::SamePokemon);
+        }
+        let pokemon1 = self.get(id1).ok_or(BreedingRefusal::UnknownPokemon(id1))?;
+        let pokemon2 = self.get(id2).ok_or(BreedingRefusal::UnknownPokemon(id2))?;
+        // Un couple refusé ne doit pas faire avancer le tirage de la session
+        let relatedness = self.check_relatedness(id1, id2)?;
+        let mut baby = Pokemon::breed(pokemon1, pokemon2, rng)?;
+        if self.inbreeding.exceeds(relatedness) {
+            baby.ivs = genetics::inbreeding_depression(&baby.ivs, relatedness);
+            baby.update_stats();
+        }
+        Ok(baby)
+    }
+
+    fn attempt_breeding<R: Rng>(
+        &mut self,
+        id1: u32,
+        id2: u32,
+        rng: &mut R,
+    ) -> Result<&Egg, BreedingRefusal> {
+        let mut baby = self.conceive(id1, id2, rng)?;
+        baby.id = self.allocate_id();
+        self.eggs.push(Egg::new(baby));
+        Ok(&self.eggs[self.eggs.len() - 1])
+    }
+
+    fn battle<R: Rng>(
+        &mut self,
+        team1: &[u32],
+        team2: &[u32],
+        rng: &mut R,
+    ) -> Option<BattleOutcome> {
+        let all_ids: Vec<u32> = team1.iter().chain(team2).copied().collect();
+        if team1.is_empty()
+            || team2.is_empty()
+            || all_ids.iter().any(|&id| self.get(id).is_none())
+            || all_ids
+                .iter()
+                .enumerate()
+                .any(|(pos, id)| all_ids[..pos].contains(id))
+        {
+            return None;
         }
+
+        let mut first_team: Vec<Pokemon> = team1
+            .iter()
+            .filter_map(|&id| self.get(id).cloned())
+            .collect();
+        let mut second_team: Vec<Pokemon> = team2
+            .iter()
+            .filter_map(|&id| self.get(id).cloned())
+            .collect();
+        let outcome = battle::team_battle(&mut first_team, &mut second_team, rng);
+
+        for pokemon in first_team.into_iter().chain(second_team) {
+            if let Some(slot) = self.get_mut(pokemon.id) {
+                *slot = pokemon;
+            }
+        }
+        Some(outcome)
     }
 }
 
 fn main() {
-    let mut breeding = Breeding::new();
+    // POKEMON_SEED permet de rejouer une session enregistrée
+    let seed = std::env::var("POKEMON_SEED")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(|| rand::thread_rng().gen());
+    if std::env::args().any(|arg| arg == "--demo") {
+        demo(seed);
+    } else {
+        Shell::new(seed).run();
+    }
+}
 
-    breeding.add_pokemon(Pokemon::new(
-        "Salamèche",
-        5,
-        PokemonType::Fire,
-        Gender::Male,
-    ));
-    breeding.add_pokemon(Pokemon::new(
-        "Carapuce",
+fn demo(seed: u64) {
+    let mut breeding = Breeding::new(seed);
+    let mut rng = breeding.session_rng();
+    println!("Graine de la session: {}", seed);
+    let species_id = |name: &str| {
+        pokedex()
+            .find_by_name(name)
+            .unwrap_or_else(|| panic!("Espèce inconnue: {}", name))
+            .id
+    };
+
+    println!("Pokédex:");
+    for species in pokedex().all() {
+        println!("{}", species);
+    }
+
+    let mut salameche = Pokemon::new(species_id("Salamèche"), 5, Gender::Male, &mut rng);
+    salameche.set_nickname("Flammy");
+    let salameche = breeding.add_pokemon(salameche);
+    let carapuce = breeding.add_pokemon(Pokemon::new(
+        species_id("Carapuce"),
         6,
-        PokemonType::Water,
         Gender::Female,
+        &mut rng,
     ));
-    breeding.add_pokemon(Pokemon::new(
-        "Bulbizarre",
+    let bulbizarre = breeding.add_pokemon(Pokemon::new(
+        species_id("Bulbasaur"),
         7,
-        PokemonType::Grass,
         Gender::Male,
+        &mut rng,
     ));
-    breeding.add_pokemon(Pokemon::new(
-        "Pikachu",
+    let pikachu = breeding.add_pokemon(Pokemon::new(
+        species_id("Pikachu"),
         5,
-        PokemonType::Electric,
         Gender::Female,
+        &mut rng,
     ));
 
-    println!("Pokémons dans l'élevage:");
+    println!("\nPokémons dans l'élevage:");
     breeding.display_all();
 
     println!("\nEntraînement de tous les Pokémons (+50 XP):");
-    breeding.train_all(50);
+    for event in breeding.train_all(50) {
+        println!("{}", event);
+    }
     breeding.display_all();
 
     println!("\nTentative de reproduction entre Salamèche et Pikachu:");
-    if let Some(baby) = breeding.attempt_breeding(0, 3) {
-        println!("Nouveau Pokémon né: {}", baby);
-        breeding.add_pokemon(baby);
-    } else {
-        println!("Ces Pokémons ne peuvent pas se reproduire!");
+    match breeding.attempt_breeding(salameche, pikachu, &mut rng) {
+        Ok(egg) => println!("Un oeuf a été trouvé: {}", egg),
+        Err(reason) => println!("Reproduction impossible: {}", reason),
+    }
+
+    println!("\nTentative de reproduction entre les deux Carapuce:");
+    let carapuce_male = breeding.add_pokemon(Pokemon::new(
+        species_id("Carapuce"),
+        5,
+        Gender::Male,
+        &mut rng,
+    ));
+    match breeding.attempt_breeding(carapuce, carapuce_male, &mut rng) {
+        Ok(egg) => {
+            println!("Un oeuf a été trouvé: {}", egg);
+            let baby_ivs = egg.pokemon.ivs;
+            let mother_ivs = breeding.get(carapuce).map(|p| p.ivs).unwrap_or_default();
+            let father_ivs = breeding
+                .get(carapuce_male)
+                .map(|p| p.ivs)
+                .unwrap_or_default();
+            println!(
+                "IV de la mère: {:?}\nIV du père: {:?}\nIV du bébé: {:?}",
+                mother_ivs, father_ivs, baby_ivs
+            );
+        }
+        Err(reason) => println!("Reproduction impossible: {}", reason),
+    }
+
+    println!("\nTentative de reproduction entre Pikachu et Voltorbe:");
+    let voltorbe = breeding.add_pokemon(Pokemon::new(
+        species_id("Voltorbe"),
+        8,
+        Gender::Genderless,
+        &mut rng,
+    ));
+    match breeding.attempt_breeding(pikachu, voltorbe, &mut rng) {
+        Ok(egg) => println!("Un oeuf a été trouvé: {}", egg),
+        Err(reason) => println!("Reproduction impossible: {}", reason),
+    }
+
+    println!("\nTentative de reproduction entre Voltorbe et Métamorph:");
+    let metamorph = breeding.add_pokemon(Pokemon::new(
+        species_id("Ditto"),
+        10,
+        Gender::Genderless,
+        &mut rng,
+    ));
+    match breeding.attempt_breeding(voltorbe, metamorph, &mut rng) {
+        Ok(egg) => println!("Un oeuf a été trouvé: {}", egg),
+        Err(reason) => println!("Reproduction impossible: {}", reason),
+    }
+
+    println!("\nCombat entre Salamèche et Bulbizarre:");
+    let mut first = breeding
+        .get(salameche)
+        .cloned()
+        .expect("Salamèche est dans l'élevage");
+    let mut second = breeding
+        .get(bulbizarre)
+        .cloned()
+        .expect("Bulbizarre est dans l'élevage");
+    let outcome = battle::duel(&mut first, &mut second, &mut rng);
+    for line in &outcome.log {
+        println!("{}", line);
+    }
+
+    println!("\nCombat d'équipes: Salamèche et Carapuce contre Bulbizarre et Pikachu:");
+    match breeding.battle(&[salameche, carapuce], &[bulbizarre, pikachu], &mut rng) {
+        Some(outcome) => {
+            for line in &outcome.log {
+                println!("{}", line);
+            }
+            println!(
+                "L'équipe {} gagne en {} tours ({} XP gagnés)!",
+                match outcome.winner {
+                    Side::First => "1",
+                    Side::Second => "2",
+                },
+                outcome.turns,
+                outcome.xp_gained
+            );
+        }
+        None => println!("Équipes invalides!"),
+    }
+    for id in [salameche, carapuce, bulbizarre, pikachu] {
+        if let Some(pokemon) = breeding.get(id) {
+            println!("{}", pokemon);
+        }
+    }
+    println!(
+        "Passage au Centre Pokémon: {} Pokémons soignés.",
+        breeding.heal_all()
+    );
+
+    // Poudre Dodo endort, Étincelle paralyse : l'état reste après le combat
+    println!("\nCombat avec altérations d'état: Mystherbe contre Pikachu:");
+    let mut mystherbe = Pokemon::new(species_id("Mystherbe"), 14, Gender::Female, &mut rng);
+    let mut rival = Pokemon::new(species_id("Pikachu"), 14, Gender::Male, &mut rng);
+    let outcome = battle::duel(&mut mystherbe, &mut rival, &mut rng);
+    for line in &outcome.log {
+        println!("{}", line);
+    }
+    println!("{}\n{}", mystherbe, rival);
+
+    println!("\nEntraînement intensif de Salamèche (+3000 XP):");
+    for event in breeding.train(salameche, 3000).unwrap_or_default() {
+        println!("{}", event);
+    }
+    if let Some(pokemon) = breeding.get(salameche) {
+        if let Some(remaining) = pokemon.xp_to_next_level() {
+            println!(
+                "{} a besoin de {} XP pour atteindre le niveau suivant.",
+                pokemon.name(),
+                remaining
+            );
+        }
+    }
+
+    println!("\nMétamorph quitte l'élevage:");
+    if let Some(pokemon) = breeding.remove_pokemon(metamorph) {
+        println!("{}", pokemon);
+    }
+
+    println!("\nPromenade autour de l'élevage (200 pas):");
+    for event in breeding.walk(200) {
+        println!("{}", event);
+    }
+
+    println!("\nArbre généalogique de Carapuce:");
+    if let Some(tree) = breeding.lineage_to_text(carapuce) {
+        print!("{}", tree);
+    }
+    if let Some(baby) = breeding.descendants(carapuce).first() {
+        println!("\nAncêtres de {} n°{}:", baby.name(), baby.id);
+        for ancestor in breeding.ancestors(baby.id) {
+            println!("{}", ancestor);
+        }
+        if let Some(dot) = breeding.lineage_to_dot(baby.id) {
+            println!("\nExport Graphviz:\n{}", dot);
+        }
+    }
+
+    let baby = breeding
+        .descendants(carapuce)
+        .first()
+        .map(|p| (p.id, p.gender.clone()));
+    if let Some((baby, gender)) = baby {
+        breeding.train(baby, 500);
+        let parent = if gender == Gender::Female {
+            carapuce_male
+        } else {
+            carapuce
+        };
+        println!(
+            "\nReproduction entre Carapuce n°{} et son parent n°{} (parenté: {:.2}):",
+            baby,
+            parent,
+            breeding.relatedness(baby, parent)
+        );
+        match breeding.attempt_breeding(baby, parent, &mut rng) {
+            Ok(egg) => println!("Un oeuf a été trouvé: {}", egg),
+            Err(reason) => println!("Reproduction impossible: {}", reason),
+        }
+
+        println!("Nouvelle tentative en tolérant la consanguinité:");
+        breeding.inbreeding.policy = InbreedingPolicy::Penalise;
+        match breeding.attempt_breeding(baby, parent, &mut rng) {
+            Ok(egg) => println!("Un oeuf affaibli a été trouvé: IV {:?}", egg.pokemon.ivs),
+            Err(reason) => println!("Reproduction impossible: {}", reason),
+        }
+        breeding.inbreeding = InbreedingRules::default();
+    }
+
+    println!("\nPlan d'élevage pour un Carapuce robuste qui connaît Pistolet à O:");
+    let target = BreedingTarget {
+        species: Some(species_id("Carapuce")),
+        min_ivs: Stats {
+            hp: 25,
+            defense: 25,
+            speed: 25,
+            ..Stats::default()
+        },
+        nature: None,
+        move_name: Some("Pistolet à O".to_string()),
+    };
+    match Planner::new(seed).plan(&breeding, &target) {
+        Some(plan) => println!("{}", plan),
+        None => println!("Aucun plan trouvé."),
+    }
+
+    println!("\nSimulation de l'élevage sur 8 générations (CSV):");
+    let mut simulated = breeding.clone();
+    let config = SimulationConfig {
+        generations: 8,
+        release_policy: ReleasePolicy::Oldest,
+        ..SimulationConfig::default()
+    };
+    print!(
+        "{}",
+        simulation::to_csv(&simulation::run(&mut simulated, &config, &mut rng))
+    );
+
+    println!("\nÉquipe du dresseur:");
+    for pokemon in breeding.party() {
+        println!("{}", pokemon);
+    }
+    match breeding.trainer.deposit(pikachu) {
+        Ok(location) => println!("Pikachu est déposé au PC ({}).", location),
+        Err(e) => println!("Dépôt impossible: {}", e),
+    }
+    if let Some(id) = breeding.trainer.boxes[0].slots[0] {
+        match breeding.trainer.withdraw(id) {
+            Ok(location) => println!("Le Pokémon n°{} rejoint l'équipe ({}).", id, location),
+            Err(e) => println!("Retrait impossible: {}", e),
+        }
+    }
+    let moved = breeding
+        .trainer
+        .rename_box(2, "Reproducteurs")
+        .and_then(|_| breeding.trainer.move_to(carapuce_male, 2, 1));
+    if let Err(e) = moved {
+        println!("Déplacement impossible: {}", e);
+    }
+    println!("Boîtes du PC:\n{}", breeding.describe_boxes());
+
+    println!("\nZones de rencontre:");
+    for zone in zones().all() {
+        println!("{}", zone);
+    }
+    if let Some(zone) = zones().find("Forêt de Jade") {
+        println!("\nPromenade dans {}:", zone.name);
+        for _ in 0..3 {
+            if let Some(wild) = zone.roll(&mut rng) {
+                println!("Un {} sauvage apparaît ! {}", wild.name(), wild);
+            }
+        }
+    }
+
+    // Un Pokémon affaibli au tiers de ses PV et paralysé se capture plus facilement
+    if let Some(mut wild) = zones()
+        .find("route-victoire")
+        .and_then(|zone| zone.roll(&mut rng))
+    {
+        wild.take_damage(wild.stats.hp * 2 / 3);
+        wild.inflict(StatusCondition::Paralysis);
+        println!("\nCapture d'un {} sauvage: {}", wild.name(), wild);
+        for ball in Ball::ALL {
+            println!(
+                "  {}: {:.1}% de chances",
+                ball,
+                catch_probability(&wild, ball) * 100.0
+            );
+        }
+        for condition in StatusCondition::ALL {
+            let mut affected = wild.clone();
+            affected.status = Some(condition);
+            println!(
+                "  {} avec une Poké Ball si {}: {:.1}%",
+                wild.name(),
+                condition,
+                catch_probability(&affected, Ball::Poke) * 100.0
+            );
+        }
+        for ball in Ball::ALL {
+            let result = breeding.capture(wild.clone(), ball, &mut rng);
+            println!("{} lancée... {}", ball, result);
+            if let CaptureResult::Caught(id) = result {
+                if let Some(caught) = breeding.get(id) {
+                    println!("{}", caught);
+                }
+                break;
+            }
+        }
+    }
+
+    // Match miroir : la même équipe des deux côtés, jouée au hasard d'un côté
+    // et par chaque niveau d'IA de l'autre
+    let mut rival = Breeding::new(seed);
+    for (name, gender) in [
+        ("Goupix", Gender::Female),
+        ("Ptitard", Gender::Male),
+        ("Mystherbe", Gender::Female),
+    ] {
+        rival.add_pokemon(Pokemon::new(species_id(name), 18, gender, &mut rng));
+    }
+    println!("\nTournoi contre l'ordinateur (20 matchs miroirs par niveau):");
+    for difficulty in [
+        Difficulty::Random,
+        Difficulty::Greedy,
+        Difficulty::Lookahead,
+    ] {
+        let mut rival_wins = 0;
+        for round in 0..20 {
+            let (Some(mut mirror), Some(mut opponent)) = (
+                Opponent::from_breeding(
+                    "Miroir",
+                    &rival,
+                    Difficulty::Random,
+                    seed.wrapping_add(round),
+                ),
+                Opponent::from_breeding("Rival", &rival, difficulty, seed.wrapping_add(round)),
+            ) else {
+                break;
+            };
+            if ai::play(&mut mirror, &mut opponent, &mut rng).winner == Side::Second {
+                rival_wins += 1;
+            }
+        }
+        println!("Rival {}: {} victoires sur 20", difficulty, rival_wins);
+    }
+    if let (Some(mut player), Some(mut opponent)) = (
+        Opponent::from_breeding("Élevage", &breeding, Difficulty::Greedy, seed),
+        Opponent::from_breeding("Rival", &rival, Difficulty::Lookahead, seed),
+    ) {
+        println!();
+        let outcome = ai::play(&mut player, &mut opponent, &mut rng);
+        for line in &outcome.log {
+            println!("{}", line);
+        }
+        println!(
+            "Victoire de {} en {} tours !",
+            match outcome.winner {
+                Side::First => &player.name,
+                Side::Second => &opponent.name,
+            },
+            outcome.turns
+        );
     }
 
     println!("\nÉtat final de l'élevage:");
@@ -212,7 +1179,10 @@ fn main() {
     }
 
     match Breeding::load_from_file("pokemon_save.json") {
-        Ok(loaded_breeding) => {
+        Ok((loaded_breeding, restoration)) => {
+            if let Some(restoration) = restoration {
+                println!("Attention: {}", restoration);
+            }
             println!("\nChargement de la sauvegarde:");
             loaded_breeding.display_all();
 
@@ -225,7 +1195,45 @@ fn main() {
             for pokemon in loaded_breeding.filter_by_type(&PokemonType::Fire) {
                 println!("{}", pokemon);
             }
+
+            println!("\nPokémons de type Poison:");
+            for pokemon in loaded_breeding.filter_by_type(&PokemonType::Poison) {
+                println!("{}", pokemon);
+            }
+
+            let request = "type=Water and level>=5 or gender=Female sort by level desc limit 5";
+            println!("\nRequête « {} »:", request);
+            match Query::parse(request) {
+                Ok(query) => {
+                    for pokemon in loaded_breeding.query(&query) {
+                        println!("{}", pokemon);
+                    }
+                }
+                Err(e) => println!("Requête invalide: {}", e),
+            }
         }
         Err(e) => println!("Erreur lors du chargement: {}", e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breed_with_seed(seed: u64) -> serde_json::Value {
+        let mut breeding = Breeding::new(seed);
+        let mut rng = breeding.session_rng();
+        let carapuce = pokedex().find_by_name("Carapuce").unwrap().id;
+        let mother = breeding.add_pokemon(Pokemon::new(carapuce, 10, Gender::Female, &mut rng));
+        let father = breeding.add_pokemon(Pokemon::new(carapuce, 10, Gender::Male, &mut rng));
+        let egg = breeding
+            .attempt_breeding(mother, father, &mut rng)
+            .unwrap_or_else(|refusal| panic!("{}", refusal));
+        serde_json::to_value(&egg.pokemon).unwrap()
+    }
+
+    #[test]
+    fn same_seed_gives_same_offspring() {
+        assert_eq!(breed_with_seed(42), breed_with_seed(42));
+    }
+}
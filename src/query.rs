@@ -0,0 +1,529 @@
+use crate::pokedex::pokedex;
+use crate::stats::Nature;
+use crate::{Breeding, Gender, Pokemon, PokemonType};
+use std::cmp::Ordering;
+use std::fmt;
+
+// Petit langage de requêtes sur l'élevage, par exemple :
+//   type=Fire and level>=6 or gender=Female sort by level desc limit 5
+// « and » est prioritaire sur « or », les parenthèses et « not » sont permis,
+// et deux conditions côte à côte sont implicitement reliées par « and ».
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Field {
+    Id,
+    Level,
+    Generation,
+    Xp,
+    Type,
+    Gender,
+    Species,
+    Nature,
+    Name,
+}
+
+impl Field {
+    fn parse(word: &str) -> Option<Field> {
+        let field = match word.to_lowercase().as_str() {
+            "id" => Field::Id,
+            "level" | "niveau" => Field::Level,
+            "generation" => Field::Generation,
+            "xp" => Field::Xp,
+            "type" => Field::Type,
+            "gender" | "sexe" => Field::Gender,
+            "species" | "espece" | "espèce" => Field::Species,
+            "nature" => Field::Nature,
+            "name" | "nom" => Field::Name,
+            _ => return None,
+        };
+        Some(field)
+    }
+
+    fn number(&self, pokemon: &Pokemon) -> Option<u32> {
+        match self {
+            Field::Id => Some(pokemon.id),
+            Field::Level => Some(pokemon.level),
+            Field::Generation => Some(pokemon.generation),
+            Field::Xp => Some(pokemon.xp),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Field::Id => "id",
+            Field::Level => "level",
+            Field::Generation => "generation",
+            Field::Xp => "xp",
+            Field::Type => "type",
+            Field::Gender => "gender",
+            Field::Species => "species",
+            Field::Nature => "nature",
+            Field::Name => "name",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Operator {
+    fn parse(symbol: &str) -> Option<Operator> {
+        let operator = match symbol {
+            "=" | "==" => Operator::Equal,
+            "!=" => Operator::NotEqual,
+            ">" => Operator::Greater,
+            ">=" => Operator::GreaterOrEqual,
+            "<" => Operator::Less,
+            "<=" => Operator::LessOrEqual,
+            _ => return None,
+        };
+        Some(operator)
+    }
+
+    fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            Operator::Equal => ordering == Ordering::Equal,
+            Operator::NotEqual => ordering != Ordering::Equal,
+            Operator::Greater => ordering == Ordering::Greater,
+            Operator::GreaterOrEqual => ordering != Ordering::Less,
+            Operator::Less => ordering == Ordering::Less,
+            Operator::LessOrEqual => ordering != Ordering::Greater,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Number(u32),
+    Type(PokemonType),
+    Gender(Gender),
+    Species(u32),
+    Nature(Nature),
+    Text(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Predicate {
+    Compare {
+        field: Field,
+        operator: Operator,
+        value: Value,
+    },
+    Not(Box<Predicate>),
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+}
+
+impl Predicate {
+    pub fn matches(&self, pokemon: &Pokemon) -> bool {
+        match self {
+            Predicate::Not(inner) => !inner.matches(pokemon),
+            Predicate::And(left, right) => left.matches(pokemon) && right.matches(pokemon),
+            Predicate::Or(left, right) => left.matches(pokemon) || right.matches(pokemon),
+            Predicate::Compare {
+                field,
+                operator,
+                value,
+            } => {
+                // Les champs textuels n'acceptent que = et !=, vérifié à l'analyse
+                let equal = match value {
+                    Value::Number(number) => {
+                        let actual = field.number(pokemon).unwrap_or_default();
+                        return operator.accepts(actual.cmp(number));
+                    }
+                    Value::Type(pokemon_type) => pokemon.has_type(pokemon_type),
+                    Value::Gender(gender) => &pokemon.gender == gender,
+                    Value::Species(species) => pokemon.species == *species,
+                    Value::Nature(nature) => &pokemon.nature == nature,
+                    Value::Text(name) => pokemon.name().to_lowercase() == name.to_lowercase(),
+                };
+                equal == (*operator == Operator::Equal)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Query {
+    pub predicate: Option<Predicate>,
+    pub sort: Option<(Field, SortOrder)>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum QueryError {
+    UnexpectedEnd,
+    UnexpectedToken(String),
+    UnknownField(String),
+    InvalidValue(Field, String),
+    UnsupportedOperator(Field, String),
+    InvalidLimit(String),
+    UnterminatedString(String),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QueryError::UnexpectedEnd => write!(f, "requête incomplète"),
+            QueryError::UnexpectedToken(token) => write!(f, "« {} » inattendu", token),
+            QueryError::UnknownField(field) => write!(
+                f,
+                "champ « {} » inconnu (id, level, generation, xp, type, gender, species, nature, name)",
+                field
+            ),
+            QueryError::InvalidValue(field, value) => {
+                write!(f, "« {} » n'est pas une valeur valide pour {}", value, field)
+            }
+            QueryError::UnsupportedOperator(field, operator) => write!(
+                f,
+                "l'opérateur « {} » ne s'applique pas à {}, seuls = et != sont permis",
+                operator, field
+            ),
+            QueryError::InvalidLimit(value) => {
+                write!(f, "« {} » n'est pas une limite valide", value)
+            }
+            QueryError::UnterminatedString(text) => {
+                write!(f, "guillemet fermant manquant après « \"{} »", text)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Token {
+    Word(String),
+    Operator(String),
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Word(word) | Token::Operator(word) => write!(f, "{}", word),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' || c == ')' {
+            chars.next();
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+        } else if "=!<>".contains(c) {
+            let mut symbol = String::new();
+            while let Some(&c) = chars.peek().filter(|c| "=!<>".contains(**c)) {
+                symbol.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Operator(symbol));
+        } else if c == '"' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some(c) => text.push(c),
+                    None => return Err(QueryError::UnterminatedString(text)),
+                }
+            }
+            tokens.push(Token::Word(text));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars
+                .peek()
+                .filter(|c| !c.is_whitespace() && !"()=!<>\"".contains(**c))
+            {
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_keyword(token: Option<&Token>, keyword: &str) -> bool {
+    matches!(token, Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token, QueryError> {
+        let token = self.peek().cloned().ok_or(QueryError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn next_word(&mut self) -> Result<String, QueryError> {
+        match self.next()? {
+            Token::Word(word) => Ok(word),
+            token => Err(QueryError::UnexpectedToken(token.to_string())),
+        }
+    }
+
+    fn at_clause_end(&self) -> bool {
+        let token = self.peek();
+        token.is_none()
+            || token == Some(&Token::Close)
+            || is_keyword(token, "sort")
+            || is_keyword(token, "limit")
+    }
+
+    fn parse_or(&mut self) -> Result<Predicate, QueryError> {
+        let mut predicate = self.parse_and()?;
+        while is_keyword(self.peek(), "or") {
+            self.position += 1;
+            predicate = Predicate::Or(Box::new(predicate), Box::new(self.parse_and()?));
+        }
+        Ok(predicate)
+    }
+
+    fn parse_and(&mut self) -> Result<Predicate, QueryError> {
+        let mut predicate = self.parse_atom()?;
+        loop {
+            if is_keyword(self.peek(), "and") {
+                self.position += 1;
+            } else if self.at_clause_end() || is_keyword(self.peek(), "or") {
+                return Ok(predicate);
+            }
+            predicate = Predicate::And(Box::new(predicate), Box::new(self.parse_atom()?));
+        }
+    }
+
+    fn parse_atom(&mut self) -> Result<Predicate, QueryError> {
+        if is_keyword(self.peek(), "not") {
+            self.position += 1;
+            return Ok(Predicate::Not(Box::new(self.parse_atom()?)));
+        }
+        if self.peek() == Some(&Token::Open) {
+            self.position += 1;
+            let predicate = self.parse_or()?;
+            return match self.next()? {
+                Token::Close => Ok(predicate),
+                token => Err(QueryError::UnexpectedToken(token.to_string())),
+            };
+        }
+
+        let name = self.next_word()?;
+        let field = Field::parse(&name).ok_or(QueryError::UnknownField(name))?;
+        let symbol = match self.next()? {
+            Token::Operator(symbol) => symbol,
+            token => return Err(QueryError::UnexpectedToken(token.to_string())),
+        };
+        let operator =
+            Operator::parse(&symbol).ok_or_else(|| QueryError::UnexpectedToken(symbol.clone()))?;
+        let raw = self.next_word()?;
+        let invalid = || QueryError::InvalidValue(field, raw.clone());
+        let value = match field {
+            Field::Id | Field::Level | Field::Generation | Field::Xp => {
+                Value::Number(raw.parse().map_err(|_| invalid())?)
+            }
+            Field::Type => Value::Type(PokemonType::from_name(&raw).ok_or_else(invalid)?),
+            Field::Gender => Value::Gender(match raw.to_lowercase().as_str() {
+                "male" | "mâle" | "m" => Gender::Male,
+                "female" | "femelle" | "f" => Gender::Female,
+                "genderless" | "asexué" => Gender::Genderless,
+                _ => return Err(invalid()),
+            }),
+            Field::Species => Value::Species(pokedex().find_by_name(&raw).ok_or_else(invalid)?.id),
+            Field::Nature => Value::Nature(Nature::from_name(&raw).ok_or_else(invalid)?),
+            Field::Name => Value::Text(raw.clone()),
+        };
+        let textual = !matches!(value, Value::Number(_));
+        if textual && !matches!(operator, Operator::Equal | Operator::NotEqual) {
+            return Err(QueryError::UnsupportedOperator(field, symbol));
+        }
+        Ok(Predicate::Compare {
+            field,
+            operator,
+            value,
+        })
+    }
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Query, QueryError> {
+        let mut parser = Parser {
+            tokens: tokenize(input)?,
+            position: 0,
+        };
+        let mut query = Query::default();
+        if !parser.at_clause_end() {
+            query.predicate = Some(parser.parse_or()?);
+        }
+
+        while let Some(token) = parser.peek().cloned() {
+            if is_keyword(Some(&token), "sort") {
+                parser.position += 1;
+                if is_keyword(parser.peek(), "by") {
+                    parser.position += 1;
+                }
+                let name = parser.next_word()?;
+                let field = Field::parse(&name).ok_or(QueryError::UnknownField(name))?;
+                let mut order = SortOrder::Ascending;
+                if is_keyword(parser.peek(), "desc") {
+                    order = SortOrder::Descending;
+                    parser.position += 1;
+                } else if is_keyword(parser.peek(), "asc") {
+                    parser.position += 1;
+                }
+                query.sort = Some((field, order));
+            } else if is_keyword(Some(&token), "limit") {
+                parser.position += 1;
+                let value = parser.next_word()?;
+                let limit = value.parse().map_err(|_| QueryError::InvalidLimit(value))?;
+                query.limit = Some(limit);
+            } else {
+                return Err(QueryError::UnexpectedToken(token.to_string()));
+            }
+        }
+        Ok(query)
+    }
+}
+
+fn compare(field: Field, a: &Pokemon, b: &Pokemon) -> Ordering {
+    match field {
+        Field::Id | Field::Level | Field::Generation | Field::Xp => {
+            field.number(a).cmp(&field.number(b))
+        }
+        Field::Type => a.pokemon_type.to_string().cmp(&b.pokemon_type.to_string()),
+        Field::Gender => format!("{:?}", a.gender).cmp(&format!("{:?}", b.gender)),
+        Field::Species => a.species.cmp(&b.species),
+        Field::Nature => a.nature.to_string().cmp(&b.nature.to_string()),
+        Field::Name => a.name().to_lowercase().cmp(&b.name().to_lowercase()),
+    }
+}
+
+impl Breeding {
+    pub fn query(&self, query: &Query) -> Vec<&Pokemon> {
+        let mut results: Vec<&Pokemon> = self
+            .pokemon_list
+            .iter()
+            .filter(|p| query.predicate.as_ref().is_none_or(|pred| pred.matches(p)))
+            .collect();
+        if let Some((field, order)) = query.sort {
+            // Tri stable : à égalité, l'ordre de l'élevage est conservé
+            results.sort_by(|a, b| match order {
+                SortOrder::Ascending => compare(field, a, b),
+                SortOrder::Descending => compare(field, b, a),
+            });
+        }
+        if let Some(limit) = query.limit {
+            results.truncate(limit);
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compare(field: Field, operator: Operator, value: Value) -> Predicate {
+        Predicate::Compare {
+            field,
+            operator,
+            value,
+        }
+    }
+
+    fn level(operator: Operator, level: u32) -> Predicate {
+        compare(Field::Level, operator, Value::Number(level))
+    }
+
+    fn predicate(input: &str) -> Predicate {
+        Query::parse(input).unwrap().predicate.unwrap()
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let expected = Predicate::Or(
+            Box::new(Predicate::And(
+                Box::new(level(Operator::Greater, 1)),
+                Box::new(level(Operator::Less, 9)),
+            )),
+            Box::new(compare(
+                Field::Type,
+                Operator::Equal,
+                Value::Type(PokemonType::Fire),
+            )),
+        );
+        assert_eq!(predicate("level>1 and level<9 or type=Fire"), expected);
+        assert_eq!(
+            predicate("level>1 and (level<9 or type=Fire)"),
+            Predicate::And(
+                Box::new(level(Operator::Greater, 1)),
+                Box::new(Predicate::Or(
+                    Box::new(level(Operator::Less, 9)),
+                    Box::new(compare(
+                        Field::Type,
+                        Operator::Equal,
+                        Value::Type(PokemonType::Fire)
+                    )),
+                )),
+            )
+        );
+    }
+
+    #[test]
+    fn adjacent_conditions_are_joined_by_and() {
+        assert_eq!(
+            predicate("level>1 not level=5"),
+            Predicate::And(
+                Box::new(level(Operator::Greater, 1)),
+                Box::new(Predicate::Not(Box::new(level(Operator::Equal, 5)))),
+            )
+        );
+    }
+
+    #[test]
+    fn textual_fields_only_accept_equality() {
+        assert_eq!(
+            Query::parse("type>Fire"),
+            Err(QueryError::UnsupportedOperator(
+                Field::Type,
+                ">".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn unterminated_string_is_an_error() {
+        assert_eq!(
+            Query::parse("name=\"Flammy"),
+            Err(QueryError::UnterminatedString("Flammy".to_string()))
+        );
+    }
+}
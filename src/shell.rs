@@ -0,0 +1,753 @@
+use crate::ai::{Difficulty, Opponent};
+use crate::battle::{Action, BattleState, Side};
+use crate::capture::{Ball, CaptureResult};
+use crate::encounter::zones;
+use crate::growth::MAX_LEVEL;
+use crate::pokedex::pokedex;
+use crate::query::{Query, QueryError};
+use crate::storage::{Restoration, SaveSlots, DEFAULT_SAVE_DIRECTORY};
+use crate::trainer::StorageError;
+use crate::{Breeding, BreedingRefusal, Gender, Pokemon};
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+const DEFAULT_SAVE_FILE: &str = "pokemon_save.json";
+const PROMPT: &str = "élevage> ";
+
+const HELP: &str = "Commandes disponibles:
+  list                              affiche les Pokémons et les oeufs
+  add <espèce> <niveau> [sexe] [surnom]
+                                    ajoute un Pokémon (sexe: male, femelle)
+  remove <id>                       relâche un Pokémon
+  train <xp>                        entraîne tout l'élevage
+  train <id> <xp>                   entraîne un seul Pokémon
+  walk <pas>                        fait avancer l'incubation des oeufs
+  heal                              soigne PV et altérations de tout l'élevage
+  breed <id> <id>                   tente une reproduction
+  filter <requête>                  ex: filter type=Fire and level>=6 or gender=Female
+                                    sort by level desc limit 5
+  tree <id>                         arbre généalogique d'un Pokémon
+  save [fichier]                    sauvegarde (pokemon_save.json par défaut)
+  load [fichier]                    charge une sauvegarde
+  party                             affiche l'équipe (6 Pokémons au maximum)
+  boxes                             affiche le contenu des boîtes du PC
+  deposit <id>                      dépose un Pokémon de l'équipe au PC
+  withdraw <id>                     retire un Pokémon du PC vers l'équipe
+  move <id> <boîte> <case>          range un Pokémon dans une case du PC
+  rename-box <boîte> <nom>          renomme une boîte du PC
+  zones                             liste les zones et leurs rencontres
+  explore <zone>                    cherche un Pokémon sauvage dans une zone
+  catch [balle]                     lance une balle sur le Pokémon sauvage
+                                    (poke, super, hyper, master)
+  slots                             liste les emplacements de sauvegarde
+  save-slot <nom>                   sauvegarde dans un emplacement nommé
+  load-slot <nom>                   charge un emplacement nommé
+  challenge <niveau> <emplacement>  défie l'équipe d'une sauvegarde jouée par
+                                    l'ordinateur (aléatoire, glouton, prévoyant)
+  attack <n>                        en combat, utilise l'attaque n
+  switch <n>                        en combat, envoie le Pokémon n de l'équipe
+  forfeit                           en combat, abandonne
+  help                              affiche cette aide
+  quit                              quitte le programme";
+
+#[derive(Debug)]
+pub enum CommandError {
+    UnknownCommand(String),
+    Usage(&'static str),
+    InvalidNumber(String),
+    UnknownSpecies(String),
+    UnknownZone(String),
+    UnknownBall(String),
+    NoWildPokemon,
+    UnknownGender(String),
+    Query(QueryError),
+    UnknownPokemon(u32),
+    Breeding(BreedingRefusal),
+    Storage(StorageError),
+    Io(io::Error),
+    UnknownDifficulty(String),
+    CannotFight(String),
+    InvalidAction(String),
+    InBattle,
+    NotInBattle,
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandError::UnknownCommand(command) => write!(
+                f,
+                "commande inconnue « {} », tapez « help » pour la liste des commandes",
+                command
+            ),
+            CommandError::Usage(usage) => write!(f, "utilisation : {}", usage),
+            CommandError::InvalidNumber(value) => {
+                write!(f, "« {} » n'est pas un nombre positif valide", value)
+            }
+            CommandError::UnknownSpecies(name) => write!(
+                f,
+                "espèce « {} » absente du Pokédex (nom français ou anglais)",
+                name
+            ),
+            CommandError::UnknownZone(name) => {
+                write!(f, "zone « {} » inconnue, voir « zones »", name)
+            }
+            CommandError::UnknownBall(name) => write!(
+                f,
+                "balle « {} » inconnue, utilisez poke, super, hyper ou master",
+                name
+            ),
+            CommandError::NoWildPokemon => {
+                write!(f, "aucun Pokémon sauvage en vue, utilisez « explore »")
+            }
+            CommandError::UnknownGender(value) => {
+                write!(f, "sexe « {} » inconnu, utilisez male ou femelle", value)
+            }
+            CommandError::Query(error) => write!(f, "requête invalide : {}", error),
+            CommandError::UnknownPokemon(id) => {
+                write!(f, "aucun Pokémon avec l'identifiant {}, voir « list »", id)
+            }
+            CommandError::Breeding(reason) => write!(f, "reproduction impossible : {}", reason),
+            CommandError::Storage(error) => write!(f, "rangement impossible : {}", error),
+            CommandError::Io(error) => write!(f, "erreur de fichier : {}", error),
+            CommandError::UnknownDifficulty(name) => write!(
+                f,
+                "niveau « {} » inconnu, utilisez aléatoire, glouton ou prévoyant",
+                name
+            ),
+            CommandError::CannotFight(trainer) => {
+                write!(f, "aucun Pokémon de {} n'est en état de combattre", trainer)
+            }
+            CommandError::InvalidAction(reason) => write!(f, "action impossible : {}", reason),
+            CommandError::InBattle => write!(
+                f,
+                "un combat est en cours, utilisez attack, switch ou forfeit"
+            ),
+            CommandError::NotInBattle => {
+                write!(f, "aucun combat en cours, utilisez « challenge »")
+            }
+        }
+    }
+}
+
+impl From<BreedingRefusal> for CommandError {
+    fn from(reason: BreedingRefusal) -> Self {
+        CommandError::Breeding(reason)
+    }
+}
+
+impl From<StorageError> for CommandError {
+    fn from(error: StorageError) -> Self {
+        CommandError::Storage(error)
+    }
+}
+
+impl From<QueryError> for CommandError {
+    fn from(error: QueryError) -> Self {
+        CommandError::Query(error)
+    }
+}
+
+impl From<io::Error> for CommandError {
+    fn from(error: io::Error) -> Self {
+        CommandError::Io(error)
+    }
+}
+
+fn parse_number(value: &str) -> Result<u32, CommandError> {
+    value
+        .parse()
+        .map_err(|_| CommandError::InvalidNumber(value.to_string()))
+}
+
+fn parse_gender(value: &str) -> Result<Gender, CommandError> {
+    match value.to_lowercase().as_str() {
+        "m" | "male" | "mâle" => Ok(Gender::Male),
+        "f" | "femelle" | "female" => Ok(Gender::Female),
+        _ => Err(CommandError::UnknownGender(value.to_string())),
+    }
+}
+
+// Prévient le joueur quand une sauvegarde précédente a remplacé le fichier demandé
+fn with_warning(restoration: Option<Restoration>, message: String) -> String {
+    match restoration {
+        Some(restoration) => format!("Attention: {}\n{}", restoration, message),
+        None => message,
+    }
+}
+
+pub struct Shell {
+    breeding: Breeding,
+    rng: StdRng,
+    slots: SaveSlots,
+    wild: Option<Pokemon>,
+    challenge: Option<Challenge>,
+}
+
+// Combat en cours contre l'ordinateur : le joueur mène le premier camp avec
+// des copies des Pokémons de son équipe, rendues à l'élevage à la fin
+struct Challenge {
+    state: BattleState,
+    opponent: Opponent,
+}
+
+impl Shell {
+    pub fn new(seed: u64) -> Self {
+        let breeding = Breeding::new(seed);
+        let rng = breeding.session_rng();
+        Shell {
+            breeding,
+            rng,
+            slots: SaveSlots::new(DEFAULT_SAVE_DIRECTORY),
+            wild: None,
+            challenge: None,
+        }
+    }
+
+    pub fn run(&mut self) {
+        println!(
+            "Élevage Pokémon (graine {}). Tapez « help » pour l'aide.",
+            self.breeding.seed
+        );
+        let stdin = io::stdin();
+        loop {
+            print!("{}", PROMPT);
+            let _ = io::stdout().flush();
+            let mut line = String::new();
+            match stdin.lock().read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            let line = line.trim();
+            if line == "quit" || line == "exit" {
+                break;
+            }
+            match self.execute(line) {
+                Ok(output) if output.is_empty() => {}
+                Ok(output) => println!("{}", output),
+                Err(error) => println!("Erreur : {}", error),
+            }
+        }
+    }
+
+    pub fn execute(&mut self, line: &str) -> Result<String, CommandError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&command, args)) = words.split_first() else {
+            return Ok(String::new());
+        };
+        let battle_command = matches!(command, "attack" | "switch" | "forfeit" | "help");
+        if self.challenge.is_some() && !battle_command {
+            return Err(CommandError::InBattle);
+        }
+        match command {
+            "help" => Ok(HELP.to_string()),
+            "list" => Ok(self.list()),
+            "add" => self.add(args),
+            "remove" => self.remove(args),
+            "train" => self.train(args),
+            "walk" => self.walk(args),
+            "heal" => Ok(self.heal()),
+            "breed" => self.breed(args),
+            "filter" => self.filter(args),
+            "tree" => self.tree(args),
+            "save" => self.save(args),
+            "load" => self.load(args),
+            "party" => Ok(self.party()),
+            "boxes" => Ok(self.breeding.describe_boxes()),
+            "deposit" => self.deposit(args),
+            "withdraw" => self.withdraw(args),
+            "move" => self.move_pokemon(args),
+            "rename-box" => self.rename_box(args),
+            "zones" => Ok(self.list_zones()),
+            "explore" => self.explore(args),
+            "catch" => self.catch(args),
+            "slots" => self.list_slots(),
+            "save-slot" => self.save_slot(args),
+            "load-slot" => self.load_slot(args),
+            "challenge" => self.start_challenge(args),
+            "attack" => self.attack(args),
+            "switch" => self.switch(args),
+            "forfeit" => self.forfeit(),
+            _ => Err(CommandError::UnknownCommand(command.to_string())),
+        }
+    }
+
+    fn list(&self) -> String {
+        let lines: Vec<String> = self
+            .breeding
+            .pokemon_list
+            .iter()
+            .map(|p| p.to_string())
+            .chain(self.breeding.eggs.iter().map(|egg| egg.to_string()))
+            .collect();
+        if lines.is_empty() {
+            return "L'élevage est vide.".to_string();
+        }
+        lines.join("\n")
+    }
+
+    fn add(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        const USAGE: &str = "add <espèce> <niveau> [male|femelle] [surnom]";
+        let [name, level, rest @ ..] = args else {
+            return Err(CommandError::Usage(USAGE));
+        };
+        let species = pokedex()
+            .find_by_name(name)
+            .ok_or_else(|| CommandError::UnknownSpecies(name.to_string()))?;
+        let level = parse_number(level)?;
+        let gender = match rest.first() {
+            Some(gender) => parse_gender(gender)?,
+            None => species.random_gender(&mut self.rng),
+        };
+        let mut pokemon =
+            Pokemon::new(species.id, level.clamp(1, MAX_LEVEL), gender, &mut self.rng);
+        if let Some(nickname) = rest.get(1) {
+            pokemon.set_nickname(nickname);
+        }
+        let id = self.breeding.add_pokemon(pokemon);
+        Ok(format!("Ajouté : {}", self.pokemon(id)?))
+    }
+
+    fn remove(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let [id] = args else {
+            return Err(CommandError::Usage("remove <id>"));
+        };
+        let id = parse_number(id)?;
+        let pokemon = self
+            .breeding
+            .remove_pokemon(id)
+            .ok_or(CommandError::UnknownPokemon(id))?;
+        Ok(format!("{} a été relâché.", pokemon.name()))
+    }
+
+    fn train(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let events = match args {
+            [xp] => self.breeding.train_all(parse_number(xp)?),
+            [id, xp] => {
+                let id = parse_number(id)?;
+                self.breeding
+                    .train(id, parse_number(xp)?)
+                    .ok_or(CommandError::UnknownPokemon(id))?
+            }
+            _ => return Err(CommandError::Usage("train <xp> ou train <id> <xp>")),
+        };
+        if events.is_empty() {
+            return Ok("Entraînement terminé.".to_string());
+        }
+        Ok(events
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn walk(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let [steps] = args else {
+            return Err(CommandError::Usage("walk <pas>"));
+        };
+        let events = self.breeding.walk(parse_number(steps)?);
+        if events.is_empty() {
+            return Ok("Aucun oeuf n'a éclos.".to_string());
+        }
+        Ok(events
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn heal(&mut self) -> String {
+        match self.breeding.heal_all() {
+            0 => "Tous les Pokémons sont déjà en pleine forme.".to_string(),
+            healed => format!("{} Pokémons ont été soignés.", healed),
+        }
+    }
+
+    fn breed(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let [first, second] = args else {
+            return Err(CommandError::Usage("breed <id> <id>"));
+        };
+        let (first, second) = (parse_number(first)?, parse_number(second)?);
+        let egg = self
+            .breeding
+            .attempt_breeding(first, second, &mut self.rng)?;
+        Ok(format!("Un oeuf a été trouvé : {}", egg))
+    }
+
+    // La ligne entière est une requête, voir le module query
+    fn filter(&self, args: &[&str]) -> Result<String, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Usage(
+                "filter type=Fire and level>=6 or gender=Female sort by level desc limit 5",
+            ));
+        }
+        let query = Query::parse(&args.join(" "))?;
+        let matches = self.breeding.query(&query);
+        if matches.is_empty() {
+            return Ok("Aucun Pokémon ne correspond.".to_string());
+        }
+        Ok(matches
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn tree(&self, args: &[&str]) -> Result<String, CommandError> {
+        let [id] = args else {
+            return Err(CommandError::Usage("tree <id>"));
+        };
+        let id = parse_number(id)?;
+        let tree = self
+            .breeding
+            .lineage_to_text(id)
+            .ok_or(CommandError::UnknownPokemon(id))?;
+        Ok(tree.trim_end().to_string())
+    }
+
+    fn save(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let filename = args.first().copied().unwrap_or(DEFAULT_SAVE_FILE);
+        self.rng = self.breeding.reseed(&mut self.rng);
+        self.breeding.save_to_file(filename)?;
+        Ok(format!("Progression sauvegardée dans {}.", filename))
+    }
+
+    fn load(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let filename = args.first().copied().unwrap_or(DEFAULT_SAVE_FILE);
+        let (breeding, restoration) = Breeding::load_from_file(filename)?;
+        self.breeding = breeding;
+        self.rng = self.breeding.session_rng();
+        Ok(with_warning(
+            restoration,
+            format!(
+                "{} Pokémons et {} oeufs chargés depuis {}.",
+                self.breeding.pokemon_list.len(),
+                self.breeding.eggs.len(),
+                filename
+            ),
+        ))
+    }
+
+    fn party(&self) -> String {
+        let party = self.breeding.party();
+        if party.is_empty() {
+            return "L'équipe est vide.".to_string();
+        }
+        party
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn deposit(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let [id] = args else {
+            return Err(CommandError::Usage("deposit <id>"));
+        };
+        let location = self.breeding.trainer.deposit(parse_number(id)?)?;
+        Ok(format!("Déposé au PC : {}.", location))
+    }
+
+    fn withdraw(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let [id] = args else {
+            return Err(CommandError::Usage("withdraw <id>"));
+        };
+        let location = self.breeding.trainer.withdraw(parse_number(id)?)?;
+        Ok(format!("Retiré du PC : {}.", location))
+    }
+
+    fn move_pokemon(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let [id, number, slot] = args else {
+            return Err(CommandError::Usage("move <id> <boîte> <case>"));
+        };
+        let (number, slot) = (parse_number(number)?, parse_number(slot)?);
+        self.breeding
+            .trainer
+            .move_to(parse_number(id)?, number as usize, slot as usize)?;
+        Ok(format!("Rangé dans la boîte {}, case {}.", number, slot))
+    }
+
+    fn rename_box(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let [number, name @ ..] = args else {
+            return Err(CommandError::Usage("rename-box <boîte> <nom>"));
+        };
+        if name.is_empty() {
+            return Err(CommandError::Usage("rename-box <boîte> <nom>"));
+        }
+        let name = name.join(" ");
+        self.breeding
+            .trainer
+            .rename_box(parse_number(number)? as usize, &name)?;
+        Ok(format!(
+            "La boîte {} s'appelle maintenant « {} ».",
+            number, name
+        ))
+    }
+
+    fn list_zones(&self) -> String {
+        zones()
+            .all()
+            .iter()
+            .map(|zone| zone.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // Le nom d'une zone peut contenir des espaces (Forêt de Jade)
+    fn explore(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::Usage("explore <zone>"));
+        }
+        let name = args.join(" ");
+        let zone = zones().find(&name).ok_or(CommandError::UnknownZone(name))?;
+        self.wild = zone.roll(&mut self.rng);
+        match &self.wild {
+            Some(wild) => Ok(format!("Un {} sauvage apparaît ! {}", wild.name(), wild)),
+            None => Ok(format!("Aucun Pokémon ne vit dans {}.", zone.name)),
+        }
+    }
+
+    // Le Pokémon sauvage reste en vue tant qu'il n'est pas capturé
+    fn catch(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let ball = match args {
+            [] => Ball::Poke,
+            _ => {
+                let name = args.join(" ");
+                Ball::from_name(&name).ok_or(CommandError::UnknownBall(name))?
+            }
+        };
+        let wild = self.wild.clone().ok_or(CommandError::NoWildPokemon)?;
+        let name = wild.name().to_string();
+        match self.breeding.capture(wild, ball, &mut self.rng) {
+            CaptureResult::Caught(id) => {
+                self.wild = None;
+                let location = self.breeding.trainer.locate(id);
+                Ok(format!(
+                    "{} lancée... {} est capturé ! (n°{}, {})",
+                    ball,
+                    name,
+                    id,
+                    location.map_or(String::new(), |l| l.to_string())
+                ))
+            }
+            result => Ok(format!("{} lancée... {}", ball, result)),
+        }
+    }
+
+    fn list_slots(&self) -> Result<String, CommandError> {
+        let names = self.slots.list()?;
+        if names.is_empty() {
+            return Ok("Aucun emplacement de sauvegarde.".to_string());
+        }
+        Ok(names.join("\n"))
+    }
+
+    fn save_slot(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let [name] = args else {
+            return Err(CommandError::Usage("save-slot <nom>"));
+        };
+        let path = self.slots.save_path(name)?;
+        self.rng = self.breeding.reseed(&mut self.rng);
+        self.breeding.save_to_file(path)?;
+        Ok(format!(
+            "Progression sauvegardée dans l'emplacement « {} ».",
+            name
+        ))
+    }
+
+    fn load_slot(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let [name] = args else {
+            return Err(CommandError::Usage("load-slot <nom>"));
+        };
+        let (breeding, restoration) = Breeding::load_from_file(self.slots.path(name)?)?;
+        self.breeding = breeding;
+        self.rng = self.breeding.session_rng();
+        Ok(with_warning(
+            restoration,
+            format!(
+                "{} Pokémons et {} oeufs chargés depuis l'emplacement « {} ».",
+                self.breeding.pokemon_list.len(),
+                self.breeding.eggs.len(),
+                name
+            ),
+        ))
+    }
+
+    fn start_challenge(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let [difficulty, name] = args else {
+            return Err(CommandError::Usage("challenge <niveau> <emplacement>"));
+        };
+        let difficulty = Difficulty::from_name(difficulty)
+            .ok_or(CommandError::UnknownDifficulty(difficulty.to_string()))?;
+        let (rival, restoration) = Breeding::load_from_file(self.slots.path(name)?)?;
+        let opponent = Opponent::from_breeding(name, &rival, difficulty, self.rng.gen())
+            .ok_or(CommandError::CannotFight(name.to_string()))?;
+        let team: Vec<Pokemon> = self.breeding.party().into_iter().cloned().collect();
+        let state = BattleState::new(team, opponent.team.clone());
+        let standing = |side| state.team(side).iter().any(|f| !f.pokemon.is_fainted());
+        if !standing(Side::First) {
+            return Err(CommandError::CannotFight("votre équipe".to_string()));
+        }
+        if !standing(Side::Second) {
+            return Err(CommandError::CannotFight(name.to_string()));
+        }
+        let intro = with_warning(
+            restoration,
+            format!(
+                "{} (niveau {}) vous défie !\n{}",
+                opponent.name,
+                opponent.difficulty,
+                describe_battle(&state, &opponent)
+            ),
+        );
+        self.challenge = Some(Challenge { state, opponent });
+        Ok(intro)
+    }
+
+    fn attack(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let [position] = args else {
+            return Err(CommandError::Usage("attack <n>"));
+        };
+        let position = parse_number(position)? as usize;
+        let challenge = self.challenge.as_ref().ok_or(CommandError::NotInBattle)?;
+        let legal = challenge.state.legal_actions(Side::First);
+        // Sans PP, n'importe quelle attaque devient Lutte
+        let action = if legal.contains(&Action::Attack(None)) {
+            Action::Attack(None)
+        } else {
+            Action::Attack(Some(position.wrapping_sub(1)))
+        };
+        if !legal.contains(&action) {
+            return Err(CommandError::InvalidAction(format!(
+                "l'attaque n°{} n'existe pas ou n'a plus de PP",
+                position
+            )));
+        }
+        Ok(self.play_turn(action))
+    }
+
+    fn switch(&mut self, args: &[&str]) -> Result<String, CommandError> {
+        let [position] = args else {
+            return Err(CommandError::Usage("switch <n>"));
+        };
+        let position = parse_number(position)? as usize;
+        let challenge = self.challenge.as_ref().ok_or(CommandError::NotInBattle)?;
+        let action = Action::Switch(position.wrapping_sub(1));
+        if !challenge.state.legal_actions(Side::First).contains(&action) {
+            return Err(CommandError::InvalidAction(format!(
+                "le Pokémon n°{} ne peut pas entrer en combat",
+                position
+            )));
+        }
+        Ok(self.play_turn(action))
+    }
+
+    fn forfeit(&mut self) -> Result<String, CommandError> {
+        let challenge = self.challenge.take().ok_or(CommandError::NotInBattle)?;
+        self.end_challenge(challenge);
+        Ok("Vous abandonnez le combat.".to_string())
+    }
+
+    // L'ordinateur choisit son action au vu du même état que le joueur
+    fn play_turn(&mut self, action: Action) -> String {
+        let Some(mut challenge) = self.challenge.take() else {
+            return String::new();
+        };
+        let reply = challenge.opponent.choose(&challenge.state, Side::Second);
+        let mut log = Vec::new();
+        challenge
+            .state
+            .play_turn([action, reply], &mut log, &mut self.rng);
+
+        match challenge.state.winner() {
+            Some(winner) => {
+                log.push(match winner {
+                    Side::First => format!("Vous avez battu {} !", challenge.opponent.name),
+                    Side::Second => format!("{} remporte le combat...", challenge.opponent.name),
+                });
+                self.end_challenge(challenge);
+            }
+            None => {
+                log.push(describe_battle(&challenge.state, &challenge.opponent));
+                self.challenge = Some(challenge);
+            }
+        }
+        log.join("\n")
+    }
+
+    // Les PV, altérations et XP gagnés pendant le combat restent acquis
+    fn end_challenge(&mut self, challenge: Challenge) {
+        let [team, _] = challenge.state.into_teams();
+        for pokemon in team {
+            if let Some(slot) = self.breeding.get_mut(pokemon.id) {
+                *slot = pokemon;
+            }
+        }
+    }
+
+    fn pokemon(&self, id: u32) -> Result<&Pokemon, CommandError> {
+        self.breeding
+            .get(id)
+            .ok_or(CommandError::UnknownPokemon(id))
+    }
+}
+
+// Les deux Pokémons actifs, puis les attaques et l'équipe du joueur
+fn describe_battle(state: &BattleState, opponent: &Opponent) -> String {
+    let player = state.active(Side::First);
+    let rival = state.active(Side::Second);
+    let mut lines = vec![
+        format!(
+            "{} de {}: {}",
+            rival.pokemon.name(),
+            opponent.name,
+            describe_health(&rival.pokemon)
+        ),
+        format!(
+            "Votre {}: {}",
+            player.pokemon.name(),
+            describe_health(&player.pokemon)
+        ),
+    ];
+    let moves: Vec<String> = player
+        .moves
+        .iter()
+        .zip(&player.pp)
+        .enumerate()
+        .map(|(index, (used_move, pp))| {
+            format!(
+                "{}. {} ({}/{})",
+                index + 1,
+                used_move.name,
+                pp,
+                used_move.pp
+            )
+        })
+        .collect();
+    lines.push(format!("Attaques: {}", moves.join(", ")));
+    let team: Vec<String> = state
+        .team(Side::First)
+        .iter()
+        .enumerate()
+        .map(|(index, fighter)| {
+            format!(
+                "{}. {} {}",
+                index + 1,
+                fighter.pokemon.name(),
+                describe_health(&fighter.pokemon)
+            )
+        })
+        .collect();
+    lines.push(format!("Équipe: {}", team.join(", ")));
+    lines.join("\n")
+}
+
+fn describe_health(pokemon: &Pokemon) -> String {
+    match pokemon.status {
+        _ if pokemon.is_fainted() => "K.O.".to_string(),
+        Some(status) => format!("PV {}/{} {}", pokemon.hp, pokemon.stats.hp, status),
+        None => format!("PV {}/{}", pokemon.hp, pokemon.stats.hp),
+    }
+}
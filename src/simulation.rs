@@ -0,0 +1,174 @@
+use crate::{Breeding, Pokemon, PokemonEvent, PokemonType};
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ReleasePolicy {
+    // Relâche les plus anciens pensionnaires en premier
+    Oldest,
+    // Relâche ceux dont la somme des IV est la plus faible
+    Weakest,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub generations: u32,
+    pub xp_per_generation: u32,
+    // Nombre de générations passées à l'élevage avant d'être relâché
+    pub max_age: u32,
+    pub max_population: usize,
+    pub release_policy: ReleasePolicy,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        SimulationConfig {
+            generations: 10,
+            xp_per_generation: 300,
+            max_age: 5,
+            max_population: 20,
+            release_policy: ReleasePolicy::Weakest,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GenerationStats {
+    pub generation: u32,
+    pub population: usize,
+    pub eggs: usize,
+    pub births: usize,
+    pub releases: usize,
+    pub mean_level: f32,
+    pub type_counts: Vec<(PokemonType, usize)>,
+}
+
+fn iv_total(pokemon: &Pokemon) -> u32 {
+    pokemon.ivs.hp + pokemon.ivs.attack + pokemon.ivs.defense + pokemon.ivs.speed
+}
+
+// Les meilleurs reproducteurs choisissent leur partenaire en premier, chaque
+// Pokémon ne pondant qu'une fois par génération
+fn select_mates(breeding: &Breeding) -> Vec<(u32, u32)> {
+    let mut candidates: Vec<&Pokemon> = breeding.pokemon_list.iter().collect();
+    candidates.sort_by_key(|p| std::cmp::Reverse(iv_total(p)));
+    let mut paired = HashSet::new();
+    let mut pairs = Vec::new();
+    for (i, first) in candidates.iter().enumerate() {
+        if paired.contains(&first.id) {
+            continue;
+        }
+        let partner = candidates[i + 1..].iter().find(|second| {
+            !paired.contains(&second.id)
+                && first.can_breed(second).is_ok()
+                && breeding.check_relatedness(first.id, second.id).is_ok()
+        });
+        if let Some(second) = partner {
+            paired.insert(first.id);
+            paired.insert(second.id);
+            pairs.push((first.id, second.id));
+        }
+    }
+    pairs
+}
+
+fn release(breeding: &mut Breeding, ages: &HashMap<u32, u32>, config: &SimulationConfig) -> usize {
+    let before = breeding.pokemon_list.len();
+    let age = |p: &Pokemon| ages.get(&p.id).copied().unwrap_or(0);
+    breeding.pokemon_list.retain(|p| age(p) <= config.max_age);
+
+    if breeding.pokemon_list.len() > config.max_population {
+        match config.release_policy {
+            ReleasePolicy::Oldest => breeding
+                .pokemon_list
+                .sort_by_key(|p| (std::cmp::Reverse(age(p)), p.id)),
+            ReleasePolicy::Weakest => breeding
+                .pokemon_list
+                .sort_by_key(|p| (iv_total(p), std::cmp::Reverse(p.id))),
+        }
+        let excess = breeding.pokemon_list.len() - config.max_population;
+        breeding.pokemon_list.drain(..excess);
+        breeding.pokemon_list.sort_by_key(|p| p.id);
+    }
+    breeding.sync_trainer();
+    before - breeding.pokemon_list.len()
+}
+
+fn snapshot(
+    breeding: &Breeding,
+    generation: u32,
+    births: usize,
+    releases: usize,
+) -> GenerationStats {
+    let population = breeding.pokemon_list.len();
+    let mean_level = if population == 0 {
+        0.0
+    } else {
+        breeding.pokemon_list.iter().map(|p| p.level).sum::<u32>() as f32 / population as f32
+    };
+    let type_counts = PokemonType::ALL
+        .iter()
+        .map(|t| (t.clone(), breeding.filter_by_type(t).len()))
+        .collect();
+    GenerationStats {
+        generation,
+        population,
+        eggs: breeding.eggs.len(),
+        births,
+        releases,
+        mean_level,
+        type_counts,
+    }
+}
+
+// Chaque génération : accouplements, entraînement (qui fait aussi éclore les
+// oeufs), vieillissement puis relâche des pensionnaires en trop
+pub fn run<R: Rng>(
+    breeding: &mut Breeding,
+    config: &SimulationConfig,
+    rng: &mut R,
+) -> Vec<GenerationStats> {
+    let mut ages: HashMap<u32, u32> = HashMap::new();
+    let mut history = vec![snapshot(breeding, 0, 0, 0)];
+
+    for generation in 1..=config.generations {
+        for (first, second) in select_mates(breeding) {
+            let _ = breeding.attempt_breeding(first, second, rng);
+        }
+        let births = breeding
+            .train_all(config.xp_per_generation)
+            .iter()
+            .filter(|event| matches!(event, PokemonEvent::Hatched { .. }))
+            .count();
+        for pokemon in &breeding.pokemon_list {
+            *ages.entry(pokemon.id).or_insert(0) += 1;
+        }
+        let releases = release(breeding, &ages, config);
+        history.push(snapshot(breeding, generation, births, releases));
+    }
+    history
+}
+
+pub fn to_csv(history: &[GenerationStats]) -> String {
+    let mut csv = String::from("generation,population,eggs,births,releases,mean_level");
+    for pokemon_type in &PokemonType::ALL {
+        csv.push_str(&format!(",{}", pokemon_type));
+    }
+    csv.push('\n');
+    for stats in history {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{:.2}",
+            stats.generation,
+            stats.population,
+            stats.eggs,
+            stats.births,
+            stats.releases,
+            stats.mean_level
+        ));
+        for (_, count) in &stats.type_counts {
+            csv.push_str(&format!(",{}", count));
+        }
+        csv.push('\n');
+    }
+    csv
+}
@@ -0,0 +1,121 @@
+use crate::find_by_name;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub const MAX_IV: u32 = 31;
+
+#[derive(Debug, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Stats {
+    pub hp: u32,
+    pub attack: u32,
+    pub defense: u32,
+    pub speed: u32,
+}
+
+impl Stats {
+    pub fn random_ivs<R: Rng>(rng: &mut R) -> Self {
+        Stats {
+            hp: rng.gen_range(0..=MAX_IV),
+            attack: rng.gen_range(0..=MAX_IV),
+            defense: rng.gen_range(0..=MAX_IV),
+            speed: rng.gen_range(0..=MAX_IV),
+        }
+    }
+
+    // Formules des jeux (sans les points d'effort) :
+    // PV = (2 * base + IV) * niveau / 100 + niveau + 10
+    // autres = ((2 * base + IV) * niveau / 100 + 5) * modificateur de nature
+    pub fn compute(base: &Stats, ivs: &Stats, level: u32, nature: &Nature) -> Self {
+        let scale = |base: u32, iv: u32| (2 * base + iv) * level / 100;
+        let (raised, lowered) = nature.modifiers();
+        let with_nature = |stat: StatKind, value: u32| {
+            if Some(stat) == raised {
+                value * 11 / 10
+            } else if Some(stat) == lowered {
+                value * 9 / 10
+            } else {
+                value
+            }
+        };
+        Stats {
+            hp: scale(base.hp, ivs.hp) + level + 10,
+            attack: with_nature(StatKind::Attack, scale(base.attack, ivs.attack) + 5),
+            defense: with_nature(StatKind::Defense, scale(base.defense, ivs.defense) + 5),
+            speed: with_nature(StatKind::Speed, scale(base.speed, ivs.speed) + 5),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum StatKind {
+    Attack,
+    Defense,
+    Speed,
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub enum Nature {
+    #[default]
+    Hardy,
+    Docile,
+    Serious,
+    Lonely,
+    Brave,
+    Bold,
+    Relaxed,
+    Timid,
+    Hasty,
+}
+
+impl Nature {
+    const ALL: [Nature; 9] = [
+        Nature::Hardy,
+        Nature::Docile,
+        Nature::Serious,
+        Nature::Lonely,
+        Nature::Brave,
+        Nature::Bold,
+        Nature::Relaxed,
+        Nature::Timid,
+        Nature::Hasty,
+    ];
+
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        Nature::ALL[rng.gen_range(0..Nature::ALL.len())].clone()
+    }
+
+    pub fn from_name(name: &str) -> Option<Nature> {
+        find_by_name(&Nature::ALL, name)
+    }
+
+    // Statistique augmentée de 10% et statistique diminuée de 10%
+    fn modifiers(&self) -> (Option<StatKind>, Option<StatKind>) {
+        match self {
+            Nature::Hardy | Nature::Docile | Nature::Serious => (None, None),
+            Nature::Lonely => (Some(StatKind::Attack), Some(StatKind::Defense)),
+            Nature::Brave => (Some(StatKind::Attack), Some(StatKind::Speed)),
+            Nature::Bold => (Some(StatKind::Defense), Some(StatKind::Attack)),
+            Nature::Relaxed => (Some(StatKind::Defense), Some(StatKind::Speed)),
+            Nature::Timid => (Some(StatKind::Speed), Some(StatKind::Attack)),
+            Nature::Hasty => (Some(StatKind::Speed), Some(StatKind::Defense)),
+        }
+    }
+}
+
+impl fmt::Display for Nature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Nature::Hardy => "Hardi",
+            Nature::Docile => "Docile",
+            Nature::Serious => "Sérieux",
+            Nature::Lonely => "Solo",
+            Nature::Brave => "Brave",
+            Nature::Bold => "Assuré",
+            Nature::Relaxed => "Relax",
+            Nature::Timid => "Timide",
+            Nature::Hasty => "Pressé",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{Breeding, Pokemon, PokemonType};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const THAW_CHANCE: f64 = 0.2;
+const WAKE_UP_CHANCE: f64 = 1.0 / 3.0;
+const FULL_PARALYSIS_CHANCE: f64 = 0.25;
+const CONFUSION_SELF_HIT_CHANCE: f64 = 1.0 / 3.0;
+// Une confusion dure de 2 à 5 tours
+const CONFUSION_TURNS: std::ops::RangeInclusive<u32> = 2..=5;
+
+// Altérations d'état durables : un Pokémon n'en porte qu'une à la fois et la
+// garde après le combat, jusqu'à ce qu'il soit soigné
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum StatusCondition {
+    Burn,
+    Poison,
+    Paralysis,
+    Sleep,
+    Freeze,
+}
+
+// Ce que l'état permet au Pokémon au début de son tour
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TurnCheck {
+    Acts,
+    Skips,
+    Cured,
+}
+
+impl StatusCondition {
+    pub const ALL: [StatusCondition; 5] = [
+        StatusCondition::Burn,
+        StatusCondition::Poison,
+        StatusCondition::Paralysis,
+        StatusCondition::Sleep,
+        StatusCondition::Freeze,
+    ];
+
+    // Multiplicateur appliqué au taux de capture
+    pub fn catch_bonus(&self) -> f32 {
+        match self {
+            StatusCondition::Sleep | StatusCondition::Freeze => 2.0,
+            StatusCondition::Burn | StatusCondition::Poison | StatusCondition::Paralysis => 1.5,
+        }
+    }
+
+    // Un Pokémon Feu ne brûle pas, un Pokémon Glace ne gèle pas, etc.
+    fn immune_types(&self) -> &'static [PokemonType] {
+        match self {
+            StatusCondition::Burn => &[PokemonType::Fire],
+            StatusCondition::Poison => &[PokemonType::Poison, PokemonType::Steel],
+            StatusCondition::Paralysis => &[PokemonType::Electric],
+            StatusCondition::Sleep => &[],
+            StatusCondition::Freeze => &[PokemonType::Ice],
+        }
+    }
+
+    // Sommeil et gel empêchent d'agir jusqu'au réveil ou au dégel, la
+    // paralysie fait parfois passer un tour
+    pub fn check_turn<R: Rng>(&self, rng: &mut R) -> TurnCheck {
+        match self {
+            StatusCondition::Sleep if rng.gen_bool(WAKE_UP_CHANCE) => TurnCheck::Cured,
+            StatusCondition::Freeze if rng.gen_bool(THAW_CHANCE) => TurnCheck::Cured,
+            StatusCondition::Sleep | StatusCondition::Freeze => TurnCheck::Skips,
+            StatusCondition::Paralysis if rng.gen_bool(FULL_PARALYSIS_CHANCE) => TurnCheck::Skips,
+            _ => TurnCheck::Acts,
+        }
+    }
+
+    // PV perdus à la fin de chaque tour : 1/16 pour une brûlure, 1/8 pour un poison
+    pub fn residual_damage(&self, max_hp: u32) -> u32 {
+        match self {
+            StatusCondition::Burn => (max_hp / 16).max(1),
+            StatusCondition::Poison => (max_hp / 8).max(1),
+            _ => 0,
+        }
+    }
+
+    pub fn describe_turn(&self, name: &str, check: TurnCheck) -> String {
+        match (self, check) {
+            (StatusCondition::Sleep, TurnCheck::Cured) => format!("{} se réveille !", name),
+            (StatusCondition::Sleep, _) => format!("{} dort profondément.", name),
+            (StatusCondition::Freeze, TurnCheck::Cured) => format!("{} dégèle !", name),
+            (StatusCondition::Freeze, _) => format!("{} est gelé !", name),
+            (_, _) => format!("{} est paralysé ! Il ne peut pas attaquer !", name),
+        }
+    }
+
+    pub fn describe_damage(&self, name: &str, amount: u32) -> String {
+        match self {
+            StatusCondition::Burn => format!("{} souffre de sa brûlure ({} PV).", name, amount),
+            _ => format!("{} souffre du poison ({} PV).", name, amount),
+        }
+    }
+}
+
+impl fmt::Display for StatusCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            StatusCondition::Burn => "brûlé",
+            StatusCondition::Poison => "empoisonné",
+            StatusCondition::Paralysis => "paralysé",
+            StatusCondition::Sleep => "endormi",
+            StatusCondition::Freeze => "gelé",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Altération infligée par une attaque ; la confusion est passagère et
+// disparaît à la fin du combat
+#[derive(Debug, PartialEq, Clone, Copy, Deserialize)]
+pub enum Ailment {
+    Status(StatusCondition),
+    Confusion,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct MoveEffect {
+    pub ailment: Ailment,
+    // Pourcentage de chances que l'effet se déclenche quand l'attaque touche
+    pub chance: u32,
+}
+
+// Tours de confusion restants d'un combattant
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Confusion {
+    turns: u32,
+}
+
+impl Confusion {
+    pub fn is_active(&self) -> bool {
+        self.turns > 0
+    }
+
+    pub fn start<R: Rng>(&mut self, rng: &mut R) -> bool {
+        if self.is_active() {
+            return false;
+        }
+        self.turns = rng.gen_range(CONFUSION_TURNS);
+        true
+    }
+
+    // Décompte un tour ; renvoie None si la confusion vient de se dissiper,
+    // sinon si le Pokémon se blesse lui-même
+    pub fn check_turn<R: Rng>(&mut self, rng: &mut R) -> Option<bool> {
+        self.turns -= 1;
+        if self.turns == 0 {
+            return None;
+        }
+        Some(rng.gen_bool(CONFUSION_SELF_HIT_CHANCE))
+    }
+}
+
+impl Pokemon {
+    pub fn is_fainted(&self) -> bool {
+        self.hp == 0
+    }
+
+    // Échoue si le Pokémon porte déjà une altération, est K.O. ou immunisé
+    pub fn inflict(&mut self, status: StatusCondition) -> bool {
+        let immune = status
+            .immune_types()
+            .iter()
+            .any(|pokemon_type| self.has_type(pokemon_type));
+        if self.status.is_some() || self.is_fainted() || immune {
+            return false;
+        }
+        self.status = Some(status);
+        true
+    }
+
+    pub fn cure(&mut self) {
+        self.status = None;
+    }
+
+    // Centre Pokémon : PV au maximum et altération soignée
+    pub fn heal(&mut self) {
+        self.hp = self.stats.hp;
+        self.cure();
+    }
+
+    // Un Pokémon K.O. perd son altération d'état
+    pub fn take_damage(&mut self, amount: u32) {
+        self.hp = self.hp.saturating_sub(amount);
+        if self.is_fainted() {
+            self.cure();
+        }
+    }
+
+    // La brûlure divise l'Attaque par deux
+    pub fn effective_attack(&self) -> u32 {
+        match self.status {
+            Some(StatusCondition::Burn) => self.stats.attack / 2,
+            _ => self.stats.attack,
+        }
+    }
+
+    // La paralysie divise la Vitesse par deux
+    pub fn effective_speed(&self) -> u32 {
+        match self.status {
+            Some(StatusCondition::Paralysis) => self.stats.speed / 2,
+            _ => self.stats.speed,
+        }
+    }
+}
+
+impl Breeding {
+    // Soigne tout l'élevage et renvoie le nombre de Pokémons qui en avaient besoin
+    pub fn heal_all(&mut self) -> usize {
+        let mut healed = 0;
+        for pokemon in self.pokemon_list.iter_mut() {
+            if pokemon.hp < pokemon.stats.hp || pokemon.status.is_some() {
+                pokemon.heal();
+                healed += 1;
+            }
+        }
+        healed
+    }
+}
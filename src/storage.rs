@@ -0,0 +1,219 @@
+use serde_json::Value;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+// Nombre de sauvegardes précédentes conservées à côté de chaque fichier
+pub const MAX_BACKUPS: u32 = 3;
+pub const DEFAULT_SAVE_DIRECTORY: &str = "saves";
+const CHECKSUM_KEY: &str = "checksum";
+
+// FNV-1a 64 bits sur le JSON compact, sans la somme elle-même : suffisant pour
+// détecter un fichier tronqué ou modifié à la main
+fn checksum(save: &Value) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in save.to_string().bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+pub fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+pub fn backup_path(path: &Path, number: u32) -> PathBuf {
+    with_suffix(path, &format!(".{}.bak", number))
+}
+
+// La plus ancienne sauvegarde disparaît, les autres reculent d'un cran et le
+// fichier actuel devient la sauvegarde n°1
+fn rotate_backups(path: &Path) -> io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    for number in (1..MAX_BACKUPS).rev() {
+        let older = backup_path(path, number);
+        if older.exists() {
+            fs::rename(&older, backup_path(path, number + 1))?;
+        }
+    }
+    fs::copy(path, backup_path(path, 1))?;
+    Ok(())
+}
+
+// Écrit d'abord dans un fichier temporaire puis le renomme : un arrêt brutal
+// en pleine écriture laisse l'ancienne sauvegarde intacte
+pub fn write_atomic(path: &Path, mut save: Value) -> io::Result<()> {
+    let object = save
+        .as_object_mut()
+        .ok_or_else(|| invalid_data("La sauvegarde doit être un objet JSON".to_string()))?;
+    object.remove(CHECKSUM_KEY);
+    let sum = checksum(&save);
+    if let Some(object) = save.as_object_mut() {
+        object.insert(CHECKSUM_KEY.to_string(), sum.into());
+    }
+    let json = serde_json::to_string_pretty(&save)?;
+
+    let temporary = with_suffix(path, ".tmp");
+    let mut file = fs::File::create(&temporary)?;
+    file.write_all(json.as_bytes())?;
+    file.sync_all()?;
+    rotate_backups(path)?;
+    fs::rename(&temporary, path)
+}
+
+// Les sauvegardes antérieures à la somme de contrôle sont acceptées telles quelles
+fn read_verified(path: &Path) -> io::Result<Value> {
+    let content = fs::read_to_string(path)?;
+    let mut save: Value = serde_json::from_str(&content)?;
+    let expected = save
+        .as_object_mut()
+        .ok_or_else(|| invalid_data("La sauvegarde doit être un objet JSON".to_string()))?
+        .remove(CHECKSUM_KEY);
+    if let Some(expected) = expected {
+        if expected.as_str() != Some(checksum(&save).as_str()) {
+            return Err(invalid_data(format!(
+                "Somme de contrôle invalide pour {}",
+                path.display()
+            )));
+        }
+    }
+    Ok(save)
+}
+
+// Sauvegarde précédente lue à la place d'un fichier absent ou corrompu
+pub struct Restoration {
+    pub path: PathBuf,
+    pub backup: PathBuf,
+    pub error: io::Error,
+}
+
+impl fmt::Display for Restoration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} est illisible ({}), restauration depuis {}",
+            self.path.display(),
+            self.error,
+            self.backup.display()
+        )
+    }
+}
+
+// Lit la sauvegarde ou, si elle est absente ou corrompue, la plus récente des
+// sauvegardes précédentes encore valide
+pub fn read_with_fallback(path: &Path) -> io::Result<(Value, Option<Restoration>)> {
+    let error = match read_verified(path) {
+        Ok(save) => return Ok((save, None)),
+        Err(error) => error,
+    };
+    for number in 1..=MAX_BACKUPS {
+        let backup = backup_path(path, number);
+        if let Ok(save) = read_verified(&backup) {
+            let restoration = Restoration {
+                path: path.to_path_buf(),
+                backup,
+                error,
+            };
+            return Ok((save, Some(restoration)));
+        }
+    }
+    Err(error)
+}
+
+// Emplacements de sauvegarde nommés, un fichier JSON par emplacement
+pub struct SaveSlots {
+    directory: PathBuf,
+}
+
+impl SaveSlots {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        SaveSlots {
+            directory: directory.into(),
+        }
+    }
+
+    pub fn path(&self, name: &str) -> io::Result<PathBuf> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Nom d'emplacement invalide: « {} » (lettres, chiffres, - et _)",
+                    name
+                ),
+            ));
+        }
+        Ok(self.directory.join(format!("{}.json", name)))
+    }
+
+    // Le dossier des emplacements n'est créé qu'au moment d'y sauvegarder
+    pub fn save_path(&self, name: &str) -> io::Result<PathBuf> {
+        let path = self.path(name)?;
+        fs::create_dir_all(&self.directory)?;
+        Ok(path)
+    }
+
+    pub fn list(&self) -> io::Result<Vec<String>> {
+        if !self.directory.exists() {
+            return Ok(Vec::new());
+        }
+        let mut names = Vec::new();
+        for entry in fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn falls_back_to_newest_valid_backup() {
+        let directory =
+            std::env::temp_dir().join(format!("pokemon-storage-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("save.json");
+
+        write_atomic(&path, json!({ "round": 1 })).unwrap();
+        write_atomic(&path, json!({ "round": 2 })).unwrap();
+        let (save, restoration) = read_with_fallback(&path).unwrap();
+        assert_eq!(save, json!({ "round": 2 }));
+        assert!(restoration.is_none());
+
+        // JSON valide mais modifié à la main : seule la somme de contrôle le trahit
+        let tampered = fs::read_to_string(&path).unwrap().replace("2", "3");
+        fs::write(&path, tampered).unwrap();
+        let (save, restoration) = read_with_fallback(&path).unwrap();
+        assert_eq!(save, json!({ "round": 1 }));
+        let restoration = restoration.unwrap();
+        assert_eq!(restoration.path, path);
+        assert_eq!(restoration.backup, backup_path(&path, 1));
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,324 @@
+use crate::{Breeding, Pokemon};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub const MAX_PARTY_SIZE: usize = 6;
+pub const BOX_CAPACITY: usize = 30;
+const DEFAULT_BOX_COUNT: usize = 8;
+
+// Les boîtes et leurs cases sont numérotées à partir de 1, comme dans les jeux
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Location {
+    Party(usize),
+    Box { number: usize, slot: usize },
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Location::Party(position) => write!(f, "équipe, position {}", position),
+            Location::Box { number, slot } => write!(f, "boîte {}, case {}", number, slot),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum StorageError {
+    UnknownPokemon(u32),
+    PartyFull,
+    LastPartyMember,
+    AlreadyInParty(u32),
+    UnknownBox(usize),
+    UnknownSlot(usize),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StorageError::UnknownPokemon(id) => {
+                write!(f, "aucun Pokémon avec l'identifiant {}", id)
+            }
+            StorageError::PartyFull => write!(
+                f,
+                "l'équipe est complète ({} Pokémons au maximum)",
+                MAX_PARTY_SIZE
+            ),
+            StorageError::LastPartyMember => {
+                write!(f, "le dernier Pokémon de l'équipe ne peut pas être déposé")
+            }
+            StorageError::AlreadyInParty(id) => {
+                write!(f, "le Pokémon {} est déjà dans l'équipe", id)
+            }
+            StorageError::UnknownBox(number) => write!(f, "la boîte {} n'existe pas", number),
+            StorageError::UnknownSlot(slot) => {
+                write!(f, "la case {} n'existe pas (1 à {})", slot, BOX_CAPACITY)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PcBox {
+    pub name: String,
+    pub slots: Vec<Option<u32>>,
+}
+
+impl PcBox {
+    fn new(number: usize) -> Self {
+        PcBox {
+            name: format!("Boîte {}", number),
+            slots: vec![None; BOX_CAPACITY],
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.slots.iter().flatten().count()
+    }
+}
+
+// Disposition des Pokémons de l'élevage : l'équipe active et les boîtes du
+// PC. Les Pokémons eux-mêmes restent dans Breeding::pokemon_list, on ne range
+// ici que leurs identifiants.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Trainer {
+    pub party: Vec<u32>,
+    pub boxes: Vec<PcBox>,
+}
+
+impl Default for Trainer {
+    fn default() -> Self {
+        Trainer {
+            party: Vec::new(),
+            boxes: (1..=DEFAULT_BOX_COUNT).map(PcBox::new).collect(),
+        }
+    }
+}
+
+impl Trainer {
+    pub fn locate(&self, id: u32) -> Option<Location> {
+        if let Some(position) = self.party.iter().position(|&p| p == id) {
+            return Some(Location::Party(position + 1));
+        }
+        self.boxes.iter().enumerate().find_map(|(index, pc_box)| {
+            let slot = pc_box.slots.iter().position(|&s| s == Some(id))?;
+            Some(Location::Box {
+                number: index + 1,
+                slot: slot + 1,
+            })
+        })
+    }
+
+    // Première case libre du PC ; une nouvelle boîte est ajoutée si tout est plein
+    fn store(&mut self, id: u32) -> Location {
+        for (index, pc_box) in self.boxes.iter_mut().enumerate() {
+            if let Some(slot) = pc_box.slots.iter().position(Option::is_none) {
+                pc_box.slots[slot] = Some(id);
+                return Location::Box {
+                    number: index + 1,
+                    slot: slot + 1,
+                };
+            }
+        }
+        let mut pc_box = PcBox::new(self.boxes.len() + 1);
+        pc_box.slots[0] = Some(id);
+        self.boxes.push(pc_box);
+        Location::Box {
+            number: self.boxes.len(),
+            slot: 1,
+        }
+    }
+
+    // Un nouveau Pokémon rejoint l'équipe s'il reste de la place, le PC sinon
+    pub fn place(&mut self, id: u32) -> Location {
+        if let Some(location) = self.locate(id) {
+            return location;
+        }
+        if self.party.len() < MAX_PARTY_SIZE {
+            self.party.push(id);
+            return Location::Party(self.party.len());
+        }
+        self.store(id)
+    }
+
+    fn remove(&mut self, id: u32) {
+        self.party.retain(|&p| p != id);
+        for slot in self.boxes.iter_mut().flat_map(|b| b.slots.iter_mut()) {
+            if *slot == Some(id) {
+                *slot = None;
+            }
+        }
+    }
+
+    // L'équipe ne reste jamais vide tant que le PC contient un Pokémon : le
+    // premier rangé la rejoint
+    fn refill_party(&mut self) {
+        if !self.party.is_empty() {
+            return;
+        }
+        let boxed = self.boxes.iter_mut().flat_map(|b| b.slots.iter_mut());
+        if let Some(id) = boxed.filter_map(Option::take).next() {
+            self.party.push(id);
+        }
+    }
+
+    // Le Pokémon quitte l'élevage
+    pub fn forget(&mut self, id: u32) {
+        self.remove(id);
+        self.refill_party();
+    }
+
+    pub fn deposit(&mut self, id: u32) -> Result<Location, StorageError> {
+        match self.locate(id) {
+            None => Err(StorageError::UnknownPokemon(id)),
+            Some(location @ Location::Box { .. }) => Ok(location),
+            Some(Location::Party(_)) if self.party.len() == 1 => Err(StorageError::LastPartyMember),
+            Some(Location::Party(_)) => {
+                self.remove(id);
+                Ok(self.store(id))
+            }
+        }
+    }
+
+    pub fn withdraw(&mut self, id: u32) -> Result<Location, StorageError> {
+        match self.locate(id) {
+            None => Err(StorageError::UnknownPokemon(id)),
+            Some(Location::Party(_)) => Err(StorageError::AlreadyInParty(id)),
+            Some(Location::Box { .. }) if self.party.len() >= MAX_PARTY_SIZE => {
+                Err(StorageError::PartyFull)
+            }
+            Some(Location::Box { .. }) => {
+                self.remove(id);
+                self.party.push(id);
+                Ok(Location::Party(self.party.len()))
+            }
+        }
+    }
+
+    // Déplace un Pokémon vers une case précise ; si elle est occupée, les deux
+    // Pokémons échangent leur place
+    pub fn move_to(&mut self, id: u32, number: usize, slot: usize) -> Result<(), StorageError> {
+        let from = self.locate(id).ok_or(StorageError::UnknownPokemon(id))?;
+        let target = self
+            .boxes
+            .get(number.wrapping_sub(1))
+            .ok_or(StorageError::UnknownBox(number))?;
+        let occupant = *target
+            .slots
+            .get(slot.wrapping_sub(1))
+            .ok_or(StorageError::UnknownSlot(slot))?;
+        let leaves_party = occupant.is_none() && matches!(from, Location::Party(_));
+        if leaves_party && self.party.len() == 1 {
+            return Err(StorageError::LastPartyMember);
+        }
+
+        match from {
+            Location::Party(position) => match occupant {
+                Some(other) => self.party[position - 1] = other,
+                None => {
+                    self.party.remove(position - 1);
+                }
+            },
+            Location::Box {
+                number: from_number,
+                slot: from_slot,
+            } => self.boxes[from_number - 1].slots[from_slot - 1] = occupant,
+        }
+        self.boxes[number - 1].slots[slot - 1] = Some(id);
+        Ok(())
+    }
+
+    pub fn rename_box(&mut self, number: usize, name: &str) -> Result<(), StorageError> {
+        let pc_box = self
+            .boxes
+            .get_mut(number.wrapping_sub(1))
+            .ok_or(StorageError::UnknownBox(number))?;
+        pc_box.name = name.to_string();
+        Ok(())
+    }
+
+    // Remet la disposition en accord avec la liste des Pokémons : les
+    // identifiants disparus sont retirés, les nouveaux venus sont rangés
+    pub fn reconcile(&mut self, ids: &[u32]) {
+        self.party.retain(|id| ids.contains(id));
+        for slot in self.boxes.iter_mut().flat_map(|b| b.slots.iter_mut()) {
+            if slot.is_some_and(|id| !ids.contains(&id)) {
+                *slot = None;
+            }
+        }
+        for &id in ids {
+            self.place(id);
+        }
+        self.refill_party();
+    }
+}
+
+impl Breeding {
+    pub fn party(&self) -> Vec<&Pokemon> {
+        self.trainer
+            .party
+            .iter()
+            .filter_map(|&id| self.get(id))
+            .collect()
+    }
+
+    pub fn sync_trainer(&mut self) {
+        let ids: Vec<u32> = self.pokemon_list.iter().map(|p| p.id).collect();
+        self.trainer.reconcile(&ids);
+    }
+
+    pub fn describe_boxes(&self) -> String {
+        let mut lines = Vec::new();
+        for (index, pc_box) in self.trainer.boxes.iter().enumerate() {
+            let content: Vec<String> = pc_box
+                .slots
+                .iter()
+                .enumerate()
+                .filter_map(|(slot, id)| {
+                    let pokemon = self.get((*id)?)?;
+                    Some(format!("[{}] {}. {}", slot + 1, pokemon.id, pokemon.name()))
+                })
+                .collect();
+            lines.push(format!(
+                "{}. {} ({}/{}){}{}",
+                index + 1,
+                pc_box.name,
+                pc_box.count(),
+                BOX_CAPACITY,
+                if content.is_empty() { "" } else { ": " },
+                content.join(", ")
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full_trainer() -> Trainer {
+        let mut trainer = Trainer::default();
+        for id in 1..=MAX_PARTY_SIZE as u32 + 1 {
+            trainer.place(id);
+        }
+        trainer
+    }
+
+    #[test]
+    fn forgetting_the_whole_party_pulls_from_the_pc() {
+        let mut trainer = full_trainer();
+        for id in 1..=MAX_PARTY_SIZE as u32 {
+            trainer.forget(id);
+        }
+        assert_eq!(trainer.party, vec![MAX_PARTY_SIZE as u32 + 1]);
+        assert_eq!(trainer.boxes[0].count(), 0);
+    }
+
+    #[test]
+    fn reconcile_refills_an_emptied_party() {
+        let mut trainer = full_trainer();
+        trainer.reconcile(&[MAX_PARTY_SIZE as u32 + 1]);
+        assert_eq!(trainer.party, vec![MAX_PARTY_SIZE as u32 + 1]);
+        assert_eq!(trainer.boxes[0].count(), 0);
+    }
+}